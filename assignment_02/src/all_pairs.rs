@@ -0,0 +1,100 @@
+use crate::bellman_ford;
+use crate::dijkstra;
+use crate::graph::Graph;
+use crate::weight::{self, Overflow, Weight};
+
+/// a matrix of distances between every pair of nodes. None indicates that
+/// there is no path between the two nodes
+pub type DistanceMatrix<W> = Vec<Vec<Option<W>>>;
+
+/// johnson's algorithm for all pairs shortest paths
+///
+/// reweights the graph using the potentials from bellman-ford so that every
+/// edge is non negative and then runs dijkstra from every node. the original
+/// distances are recovered by undoing the reweighting. returns None if the
+/// graph contains a negative cycle.
+pub fn johnson<W: Weight>(graph: &Graph<W>, verbose: bool) -> Result<Option<DistanceMatrix<W>>, Overflow> {
+    let Some(h) = bellman_ford::potentials(graph)? else {
+        return Ok(None);
+    };
+
+    if verbose {
+        println!("potentials: {h:?}");
+    }
+
+    let reweighted = bellman_ford::reweight(graph, &h)?;
+    let mut matrix = DistanceMatrix::with_capacity(graph.len());
+
+    for source in 0..graph.len() {
+        let found = dijkstra::shortest_paths(graph, source, |index, _| Some(reweighted[index]))?;
+        let mut row = Vec::with_capacity(graph.len());
+
+        for (target, dist) in found.dist.into_iter().enumerate() {
+            row.push(match dist {
+                Some(d) => Some(weight::add(weight::sub(d, h[source])?, h[target])?),
+                None => None,
+            });
+        }
+
+        matrix.push(row);
+    }
+
+    Ok(Some(matrix))
+}
+
+/// floyd-warshall for all pairs shortest paths
+///
+/// returns None if the graph contains a negative cycle which is found when
+/// a node has a negative distance to itself.
+pub fn floyd_warshall<W: Weight>(graph: &Graph<W>) -> Result<Option<DistanceMatrix<W>>, Overflow> {
+    let len = graph.len();
+    let mut matrix: DistanceMatrix<W> = vec![vec![None; len]; len];
+
+    for (index, row) in matrix.iter_mut().enumerate() {
+        row[index] = Some(W::ZERO);
+    }
+
+    for edge in &graph.edges {
+        let cell = &mut matrix[edge.u][edge.v];
+
+        match cell {
+            Some(known) if *known <= edge.weight => {},
+            _ => *cell = Some(edge.weight),
+        }
+    }
+
+    for k in 0..len {
+        // row k does not change while going through k unless there is a
+        // negative cycle which is checked for afterwards
+        let k_row = matrix[k].clone();
+
+        for row in matrix.iter_mut() {
+            let Some(i_k) = row[k] else {
+                continue;
+            };
+
+            for (cell, k_j) in row.iter_mut().zip(&k_row) {
+                let Some(k_j) = k_j else {
+                    continue;
+                };
+
+                let value = weight::add(i_k, *k_j)?;
+
+                match cell {
+                    Some(known) if *known <= value => {},
+                    _ => *cell = Some(value),
+                }
+            }
+        }
+    }
+
+    for (index, row) in matrix.iter().enumerate() {
+        if let Some(dist) = row[index] {
+            if dist < W::ZERO {
+                return Ok(None);
+            }
+        }
+    }
+
+    Ok(Some(matrix))
+}
@@ -0,0 +1,228 @@
+/// an exchange rate table between a list of currencies
+///
+/// `rates[a][b]` is the amount of currency b received for 1 unit of
+/// currency a. a rate of 0 means that there is no direct exchange.
+#[derive(Debug)]
+pub struct RateTable {
+    pub names: Vec<String>,
+    pub rates: Vec<Vec<f64>>,
+}
+
+/// a cycle of exchanges that results in more currency than it started with
+#[derive(Debug)]
+pub struct Arbitrage {
+    /// the currencies visited in order. the first currency is also the last
+    /// exchange back
+    pub cycle: Vec<usize>,
+    /// the product of the exchange rates along the cycle
+    pub profit: f64,
+}
+
+/// reads an exchange rate table from the given lines
+///
+/// the first line is the number of currencies, the second line is the name
+/// of each currency separated by spaces and the remaining lines are the rows
+/// of the table with a rate for every currency in the same order as the
+/// names. rates that are 0 or less are treated as no exchange available.
+pub fn read_rate_table<I>(lines: &mut I) -> RateTable
+where
+    I: Iterator<Item = std::io::Result<String>>
+{
+    let total: usize = {
+        let check = lines.next()
+            .expect("no currency data specified")
+            .expect("failed to read input from stdin");
+
+        let Ok(rtn) = check.trim().parse() else {
+            panic!("failed to parse total currencies line: \"{}\"", check);
+        };
+
+        rtn
+    };
+
+    if total == 0 {
+        panic!("amount of currencies specified is 0");
+    }
+
+    let names_line = lines.next()
+        .expect("missing currency names")
+        .expect("failed to read input from stdin");
+
+    let names: Vec<String> = names_line.split(' ')
+        .filter(|name| !name.is_empty())
+        .map(|name| name.to_owned())
+        .collect();
+
+    if names.len() != total {
+        panic!("currency names provided do not match the specified amount. expected: {total} given: {}", names.len());
+    }
+
+    let mut rates = Vec::with_capacity(total);
+
+    for line in lines {
+        let valid = line.expect("failed to read input from stdin");
+
+        if valid.trim().is_empty() {
+            continue;
+        }
+
+        let Some(row) = common::parse_line::<f64>(&valid) else {
+            panic!("invalid exchange rate row provided: \"{}\"", valid);
+        };
+
+        if row.len() != total {
+            panic!("exchange rate row does not match the amount of currencies. expected: {total} given: {} \"{}\"", row.len(), valid);
+        }
+
+        if row.iter().any(|rate| !rate.is_finite()) {
+            panic!("exchange rate row contains non finite values: \"{}\"", valid);
+        }
+
+        rates.push(row);
+    }
+
+    if rates.len() != total {
+        panic!("exchange rate rows provided do not match the specified amount. expected: {total} given: {}", rates.len());
+    }
+
+    RateTable {
+        names,
+        rates,
+    }
+}
+
+/// searches the rate table for exchange cycles that end with more currency
+/// than they started with
+///
+/// each rate is transformed into an edge with a weight of -ln(rate) so that
+/// a cycle with a product of rates greater than 1 becomes a negative cycle
+/// which bellman-ford is then able to find. since the weights are floating
+/// point values any relaxation has to improve a distance by more than
+/// `tolerance` to count and any cycle found has to have a profit greater
+/// than 1 + `tolerance` to be reported. this keeps rounding errors from
+/// showing up as arbitrage. each cycle is only reported once.
+pub fn find_arbitrage(table: &RateTable, tolerance: f64, verbose: bool) -> Vec<Arbitrage> {
+    let len = table.names.len();
+    let mut edges = Vec::new();
+
+    for (u, row) in table.rates.iter().enumerate() {
+        for (v, rate) in row.iter().enumerate() {
+            if u == v || *rate <= 0.0 {
+                continue;
+            }
+
+            edges.push((u, v, -rate.ln()));
+        }
+    }
+
+    // same as having a virtual source connected to every currency
+    let mut dist = vec![0.0f64; len];
+    let mut prev: Vec<Option<usize>> = vec![None; len];
+
+    for count in 1..len {
+        let mut updated = false;
+
+        for (u, v, weight) in &edges {
+            let check = dist[*u] + weight;
+
+            if check < dist[*v] - tolerance {
+                dist[*v] = check;
+                prev[*v] = Some(*u);
+                updated = true;
+            }
+        }
+
+        if verbose {
+            println!("iteration: {count} dist: {dist:?}");
+        }
+
+        if !updated {
+            break;
+        }
+    }
+
+    let mut found: Vec<Arbitrage> = Vec::new();
+
+    for (u, v, weight) in &edges {
+        if dist[*u] + weight >= dist[*v] - tolerance {
+            continue;
+        }
+
+        prev[*v] = Some(*u);
+
+        // walking back len steps guarantees that we end up inside of the
+        // cycle and not on a path leading into it
+        let mut start = *v;
+
+        for _ in 0..len {
+            let Some(p) = prev[start] else {
+                break;
+            };
+
+            start = p;
+        }
+
+        let mut cycle = vec![start];
+        let mut current = start;
+
+        while let Some(p) = prev[current] {
+            if p == start {
+                break;
+            }
+
+            cycle.push(p);
+            current = p;
+
+            if cycle.len() > len {
+                break;
+            }
+        }
+
+        if cycle.len() > len || prev[current] != Some(start) {
+            if verbose {
+                println!("failed to recover cycle from {}", table.names[*v]);
+            }
+
+            continue;
+        }
+
+        // predecessors were followed so the cycle is backwards
+        cycle.reverse();
+
+        // rotate so the smallest index is first, this way the same cycle is
+        // always represented the same way
+        let min_pos = cycle.iter()
+            .enumerate()
+            .min_by_key(|(_, index)| **index)
+            .map(|(pos, _)| pos)
+            .unwrap();
+
+        cycle.rotate_left(min_pos);
+
+        if found.iter().any(|known| known.cycle == cycle) {
+            continue;
+        }
+
+        let mut profit = 1.0;
+
+        for pos in 0..cycle.len() {
+            let from = cycle[pos];
+            let to = cycle[(pos + 1) % cycle.len()];
+
+            profit *= table.rates[from][to];
+        }
+
+        if verbose {
+            println!("cycle: {cycle:?} profit: {profit}");
+        }
+
+        if profit > 1.0 + tolerance {
+            found.push(Arbitrage {
+                cycle,
+                profit,
+            });
+        }
+    }
+
+    found
+}
@@ -0,0 +1,218 @@
+use std::collections::VecDeque;
+
+use crate::graph::Graph;
+use crate::paths::{Distance, Paths};
+use crate::weight::{self, Overflow, Weight};
+
+/// runs bellman-ford over the graph and returns every node that is either
+/// part of a negative cycle or reachable from one
+///
+/// every node starts with a distance of 0 which is the same as having a
+/// virtual source node connected to every other node with an edge weight of
+/// 0. this way every negative cycle is found regardless of which node it can
+/// be reached from. the returned list is sorted by node index.
+pub fn negative_cycle_nodes<W: Weight>(graph: &Graph<W>, verbose: bool) -> Result<Vec<usize>, Overflow> {
+    let mut dist: Vec<W> = vec![W::ZERO; graph.len()];
+
+    for count in 1..graph.len() {
+        if verbose {
+            println!("iteration: {count}");
+        }
+
+        let mut updated = false;
+
+        for edge in &graph.edges {
+            let check = weight::add(dist[edge.u], edge.weight)?;
+
+            if check < dist[edge.v] {
+                if verbose {
+                    println!("    {} -> {} w: {} | setting v dist: {check}", edge.u + 1, edge.v + 1, edge.weight);
+                }
+
+                dist[edge.v] = check;
+                updated = true;
+            }
+        }
+
+        // nothing changed so another iteration will not change anything
+        // either
+        if !updated {
+            break;
+        }
+    }
+
+    let mut in_negative_cycle = vec![false; graph.len()];
+    let mut queue = VecDeque::new();
+
+    // any edge that can still be relaxed has a negative cycle behind it so
+    // everything reachable from v is marked with a BFS
+    for edge in &graph.edges {
+        if in_negative_cycle[edge.v] || weight::add(dist[edge.u], edge.weight)? >= dist[edge.v] {
+            continue;
+        }
+
+        if verbose {
+            print!("negative cycle: {} ->", edge.v + 1);
+        }
+
+        in_negative_cycle[edge.v] = true;
+        queue.push_back(edge.v);
+
+        while let Some(current) = queue.pop_front() {
+            for next in &graph.neighbors[current] {
+                if in_negative_cycle[*next] {
+                    continue;
+                }
+
+                if verbose {
+                    print!(" {}", next + 1);
+                }
+
+                in_negative_cycle[*next] = true;
+                queue.push_back(*next);
+            }
+        }
+
+        if verbose {
+            println!();
+        }
+    }
+
+    Ok(in_negative_cycle.into_iter()
+        .enumerate()
+        .filter_map(|(index, found)| if found { Some(index) } else { None })
+        .collect())
+}
+
+/// calculates a potential for every node in the graph
+///
+/// the potential is the shortest distance to the node from a virtual source
+/// that is connected to every node with an edge weight of 0. for any edge
+/// (u, v) `weight + h[u] - h[v]` is never negative which is what johnson's
+/// algorithm uses to reweight the graph. returns None if the graph contains
+/// a negative cycle.
+pub fn potentials<W: Weight>(graph: &Graph<W>) -> Result<Option<Vec<W>>, Overflow> {
+    let mut dist: Vec<W> = vec![W::ZERO; graph.len()];
+
+    for _ in 1..graph.len() {
+        let mut updated = false;
+
+        for edge in &graph.edges {
+            let check = weight::add(dist[edge.u], edge.weight)?;
+
+            if check < dist[edge.v] {
+                dist[edge.v] = check;
+                updated = true;
+            }
+        }
+
+        if !updated {
+            break;
+        }
+    }
+
+    for edge in &graph.edges {
+        if weight::add(dist[edge.u], edge.weight)? < dist[edge.v] {
+            return Ok(None);
+        }
+    }
+
+    Ok(Some(dist))
+}
+
+/// runs bellman-ford from the given list of sources
+///
+/// every source starts with a distance of 0 so the distance to a node is the
+/// distance from the closest source. any node that is reachable from a
+/// negative cycle that is itself reachable from a source will have a
+/// distance of negative infinity.
+pub fn shortest_paths<W: Weight>(graph: &Graph<W>, sources: &[usize]) -> Result<Paths<W>, Overflow> {
+    let mut dist: Vec<Option<W>> = vec![None; graph.len()];
+    let mut prev: Vec<Option<usize>> = vec![None; graph.len()];
+
+    for source in sources {
+        dist[*source] = Some(W::ZERO);
+    }
+
+    for _ in 1..graph.len() {
+        let mut updated = false;
+
+        for (index, edge) in graph.edges.iter().enumerate() {
+            let Some(u_dist) = dist[edge.u] else {
+                continue;
+            };
+
+            let check = weight::add(u_dist, edge.weight)?;
+
+            let update = match dist[edge.v] {
+                Some(known) => check < known,
+                None => true,
+            };
+
+            if update {
+                dist[edge.v] = Some(check);
+                prev[edge.v] = Some(index);
+                updated = true;
+            }
+        }
+
+        if !updated {
+            break;
+        }
+    }
+
+    let mut rtn: Vec<Distance<W>> = dist.iter()
+        .map(|d| match d {
+            Some(value) => Distance::Finite(*value),
+            None => Distance::Unreachable,
+        })
+        .collect();
+    let mut queue = VecDeque::new();
+
+    for edge in &graph.edges {
+        let (Some(u_dist), Some(v_dist)) = (dist[edge.u], dist[edge.v]) else {
+            continue;
+        };
+
+        if rtn[edge.v] == Distance::NegativeInfinity || weight::add(u_dist, edge.weight)? >= v_dist {
+            continue;
+        }
+
+        rtn[edge.v] = Distance::NegativeInfinity;
+        queue.push_back(edge.v);
+
+        while let Some(current) = queue.pop_front() {
+            for next in &graph.neighbors[current] {
+                if rtn[*next] == Distance::NegativeInfinity {
+                    continue;
+                }
+
+                rtn[*next] = Distance::NegativeInfinity;
+                queue.push_back(*next);
+            }
+        }
+    }
+
+    for (node, d) in rtn.iter().enumerate() {
+        if *d == Distance::NegativeInfinity {
+            prev[node] = None;
+        }
+    }
+
+    Ok(Paths {
+        dist: rtn,
+        prev,
+    })
+}
+
+/// calculates `weight + h[u] - h[v]` for every edge in the graph using the
+/// given potentials. the returned list is in the same order as the edges
+pub fn reweight<W: Weight>(graph: &Graph<W>, h: &[W]) -> Result<Vec<W>, Overflow> {
+    let mut rtn = Vec::with_capacity(graph.edges.len());
+
+    for edge in &graph.edges {
+        rtn.push(weight::sub(weight::add(edge.weight, h[edge.u])?, h[edge.v])?);
+    }
+
+    Ok(rtn)
+}
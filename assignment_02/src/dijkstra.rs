@@ -0,0 +1,95 @@
+use std::collections::BinaryHeap;
+
+use crate::graph::{Edge, Graph};
+use crate::weight::{self, Overflow, Weight};
+
+/// the result of a single source shortest path search
+#[derive(Debug)]
+pub struct ShortestPaths<W> {
+    /// the distance to each node, None if the node is unreachable
+    pub dist: Vec<Option<W>>,
+    /// the index of the edge used to reach each node
+    pub prev: Vec<Option<usize>>,
+}
+
+/// an entry in the heap ordered so that the smallest distance is popped
+/// first. weights are only required to be partially ordered so anything
+/// that can not be compared is treated as equal
+struct HeapEntry<W>(W, usize);
+
+impl<W: Weight> PartialEq for HeapEntry<W> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == std::cmp::Ordering::Equal
+    }
+}
+
+impl<W: Weight> Eq for HeapEntry<W> {}
+
+impl<W: Weight> PartialOrd for HeapEntry<W> {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<W: Weight> Ord for HeapEntry<W> {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        other.0.partial_cmp(&self.0)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| other.1.cmp(&self.1))
+    }
+}
+
+/// runs dijkstra from the given source using a binary heap
+///
+/// the weight of each edge is retrieved from `weight` with the index of the
+/// edge so that callers are able to reweight or ignore edges without
+/// creating a new graph. an edge with a weight of None is not used. all
+/// weights are expected to be non negative.
+pub fn shortest_paths<W, F>(graph: &Graph<W>, source: usize, weight: F) -> Result<ShortestPaths<W>, Overflow>
+where
+    W: Weight,
+    F: Fn(usize, &Edge<W>) -> Option<W>
+{
+    let mut dist: Vec<Option<W>> = vec![None; graph.len()];
+    let mut prev: Vec<Option<usize>> = vec![None; graph.len()];
+    let mut visited = vec![false; graph.len()];
+    let mut heap = BinaryHeap::new();
+
+    dist[source] = Some(W::ZERO);
+    heap.push(HeapEntry(W::ZERO, source));
+
+    while let Some(HeapEntry(current_dist, u)) = heap.pop() {
+        // stale entries are left in the heap instead of decreasing the key
+        if visited[u] {
+            continue;
+        }
+
+        visited[u] = true;
+
+        for edge_index in &graph.outgoing[u] {
+            let edge = &graph.edges[*edge_index];
+
+            let Some(edge_weight) = weight(*edge_index, edge) else {
+                continue;
+            };
+
+            let check = weight::add(current_dist, edge_weight)?;
+
+            let update = match dist[edge.v] {
+                Some(known) => check < known,
+                None => true,
+            };
+
+            if update {
+                dist[edge.v] = Some(check);
+                prev[edge.v] = Some(*edge_index);
+                heap.push(HeapEntry(check, edge.v));
+            }
+        }
+    }
+
+    Ok(ShortestPaths {
+        dist,
+        prev,
+    })
+}
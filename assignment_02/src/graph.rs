@@ -0,0 +1,157 @@
+use crate::weight::Weight;
+
+/// a directed edge between two nodes
+#[derive(Debug, Clone)]
+pub struct Edge<W> {
+    pub u: usize,
+    pub v: usize,
+    pub weight: W,
+}
+
+pub type EdgeList<W> = Vec<Edge<W>>;
+pub type NodeRefList = Vec<usize>;
+pub type NeighborMap = Vec<NodeRefList>;
+
+/// contains the necessary information to represent a graph
+///
+/// includes a map of neighbors for a given node based on the index of that
+/// node along with a map of the outgoing edge indexes for each node
+#[derive(Debug)]
+pub struct Graph<W> {
+    pub edges: EdgeList<W>,
+    pub neighbors: NeighborMap,
+    pub outgoing: NeighborMap,
+}
+
+impl<W: Weight> Graph<W> {
+    pub fn with_nodes(nodes: usize, edges: usize) -> Self {
+        Graph {
+            edges: EdgeList::with_capacity(edges),
+            neighbors: vec![NodeRefList::new(); nodes],
+            outgoing: vec![NodeRefList::new(); nodes],
+        }
+    }
+
+    /// the total number of nodes in the graph
+    pub fn len(&self) -> usize {
+        self.neighbors.len()
+    }
+
+    pub fn add_edge(&mut self, u: usize, v: usize, weight: W) {
+        self.outgoing[u].push(self.edges.len());
+        self.edges.push(Edge { u, v, weight });
+        self.neighbors[u].push(v);
+    }
+
+    /// creates a copy of the graph with every edge reversed. the edge
+    /// indexes are the same as the original graph
+    pub fn reverse(&self) -> Graph<W> {
+        let mut rtn = Graph::with_nodes(self.len(), self.edges.len());
+
+        for edge in &self.edges {
+            rtn.add_edge(edge.v, edge.u, edge.weight);
+        }
+
+        rtn
+    }
+
+    /// creates a copy of the graph where only the last edge given between
+    /// two nodes is kept
+    pub fn without_parallel_edges(&self) -> Graph<W> {
+        let mut last = std::collections::HashMap::new();
+
+        for (index, edge) in self.edges.iter().enumerate() {
+            last.insert((edge.u, edge.v), index);
+        }
+
+        let mut rtn = Graph::with_nodes(self.len(), last.len());
+
+        for (index, edge) in self.edges.iter().enumerate() {
+            if last[&(edge.u, edge.v)] == index {
+                rtn.add_edge(edge.u, edge.v, edge.weight);
+            }
+        }
+
+        rtn
+    }
+
+    /// parses a 1 indexed node from the given string
+    pub fn parse_node(&self, value: &str) -> Option<usize> {
+        let node: usize = value.trim().parse().ok()?;
+
+        if node == 0 || node > self.len() {
+            None
+        } else {
+            Some(node - 1)
+        }
+    }
+}
+
+/// reads a graph from the given lines
+///
+/// the first line is expected to be "{nodes} {edges}" followed by the
+/// specified amount of edges in the form of "{u} {v} [weight]". nodes are
+/// 1 indexed in the input and 0 indexed in the returned graph. if no weight
+/// is given then the edge will have a weight of 1. any lines after the last
+/// edge are left in the iterator.
+pub fn read_graph<W, I>(lines: &mut I) -> Graph<W>
+where
+    W: Weight,
+    I: Iterator<Item = std::io::Result<String>>
+{
+    let graph_line = lines.next()
+        .expect("no graph data specified")
+        .expect("failed to read input from stdin");
+
+    let Some(graph_data) = common::parse_line::<usize>(&graph_line) else {
+        panic!("invalid graph line provided: \"{}\"", graph_line);
+    };
+
+    if graph_data.len() != 2 {
+        panic!("invalid graph line provided: \"{}\"", graph_line);
+    }
+
+    if graph_data[0] == 0 {
+        panic!("amount of nodes specified is 0");
+    }
+
+    let mut graph = Graph::with_nodes(graph_data[0], graph_data[1]);
+
+    while graph.edges.len() != graph_data[1] {
+        let Some(check) = lines.next() else {
+            panic!("too few edges provided. expected: {} given: {}", graph_data[1], graph.edges.len());
+        };
+
+        let line = check.expect("failed to read input from stdin");
+
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let found: Vec<&str> = line.split(' ')
+            .filter(|value| !value.is_empty())
+            .collect();
+
+        if found.len() < 2 || found.len() > 3 {
+            panic!("invalid graph edge: \"{}\"", line);
+        }
+
+        let (Some(u), Some(v)) = (graph.parse_node(found[0]), graph.parse_node(found[1])) else {
+            panic!("edge u/v values are invalid: u: {} v: {} \"{}\"", found[0], found[1], line);
+        };
+
+        let weight = if found.len() == 3 {
+            let Some(parsed) = W::parse_weight(found[2]) else {
+                panic!("edge weight is invalid: {} \"{}\"", found[2], line);
+            };
+
+            parsed
+        } else {
+            W::ONE
+        };
+
+        graph.add_edge(u, v, weight);
+    }
+
+    graph
+}
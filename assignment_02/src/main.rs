@@ -1,3 +1,379 @@
+mod graph;
+mod bellman_ford;
+mod arbitrage;
+mod dijkstra;
+mod all_pairs;
+mod paths;
+mod queries;
+mod yen;
+mod weight;
+
+use weight::{Overflow, Weight};
+
+/// the default tolerance used when comparing floating point distances
+const DEFAULT_TOLERANCE: f64 = 1e-9;
+
+/// the different modes that the program can run in
+enum Mode {
+    NegativeCycle,
+    Arbitrage,
+    Johnson,
+    FloydWarshall,
+    Sources(String),
+    Target(String),
+    Queries,
+    KShortest(usize),
+}
+
+/// the available types for edge weights
+enum WeightType {
+    I32,
+    I64,
+    I128,
+    Rational,
+    F64,
+}
+
 fn main() {
-    println!("Hello, world!");
+    let mut verbose = false;
+    let mut mode = Mode::NegativeCycle;
+    let mut tolerance = DEFAULT_TOLERANCE;
+    let mut weight_type = WeightType::I64;
+    let mut args = std::env::args();
+    args.next();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--verbose" => {
+                verbose = true;
+            },
+            "--arbitrage" => {
+                mode = Mode::Arbitrage;
+            },
+            "--johnson" => {
+                mode = Mode::Johnson;
+            },
+            "--floyd-warshall" => {
+                mode = Mode::FloydWarshall;
+            },
+            "--source" => {
+                let value = args.next()
+                    .expect("missing value for --source");
+
+                mode = Mode::Sources(value);
+            },
+            "--target" => {
+                let value = args.next()
+                    .expect("missing value for --target");
+
+                mode = Mode::Target(value);
+            },
+            "--queries" => {
+                mode = Mode::Queries;
+            },
+            "--k-shortest" => {
+                let value = args.next()
+                    .expect("missing value for --k-shortest");
+
+                let Ok(k) = value.parse::<usize>() else {
+                    panic!("invalid amount of paths provided: \"{}\"", value);
+                };
+
+                mode = Mode::KShortest(k);
+            },
+            "--weight" => {
+                let value = args.next()
+                    .expect("missing value for --weight");
+
+                weight_type = match value.as_str() {
+                    "i32" => WeightType::I32,
+                    "i64" => WeightType::I64,
+                    "i128" => WeightType::I128,
+                    "rational" => WeightType::Rational,
+                    "f64" => WeightType::F64,
+                    _ => panic!("unknown weight type: \"{}\". expected i32, i64, i128, rational or f64", value),
+                };
+            },
+            "--tolerance" => {
+                let value = args.next()
+                    .expect("missing value for --tolerance");
+
+                let Ok(parsed) = value.parse::<f64>() else {
+                    panic!("invalid tolerance provided: \"{}\"", value);
+                };
+
+                if !parsed.is_finite() || parsed < 0.0 {
+                    panic!("tolerance must be a finite non negative value: \"{}\"", value);
+                }
+
+                tolerance = parsed;
+            },
+            _ => {}
+        }
+    }
+
+    let mut lines = std::io::stdin().lines();
+
+    if let Mode::Arbitrage = mode {
+        arbitrage_main(&mut lines, tolerance, verbose);
+        return;
+    }
+
+    match weight_type {
+        WeightType::I32 => run::<i32, _>(mode, &mut lines, verbose),
+        WeightType::I64 => run::<i64, _>(mode, &mut lines, verbose),
+        WeightType::I128 => run::<i128, _>(mode, &mut lines, verbose),
+        WeightType::Rational => run::<weight::Rational, _>(mode, &mut lines, verbose),
+        WeightType::F64 => run::<f64, _>(mode, &mut lines, verbose),
+    }
+}
+
+/// runs the graph based modes with the given weight type
+fn run<W, I>(mode: Mode, lines: &mut I, verbose: bool)
+where
+    W: Weight,
+    I: Iterator<Item = std::io::Result<String>>
+{
+    let graph = graph::read_graph::<W, _>(lines);
+
+    match mode {
+        Mode::NegativeCycle => negative_cycle_main(&graph, verbose),
+        Mode::Arbitrage => unreachable!(),
+        Mode::Johnson | Mode::FloydWarshall => all_pairs_main(graph, mode, verbose),
+        Mode::Sources(list) => sources_main(&graph, &list),
+        Mode::Target(target) => target_main(&graph, &target),
+        Mode::Queries => queries_main(&graph, lines),
+        Mode::KShortest(k) => k_shortest_main(&graph, lines, k),
+    }
+}
+
+/// retrieves the result of a calculation or exits if the weights overflowed
+fn check_overflow<T>(result: Result<T, Overflow>) -> T {
+    match result {
+        Ok(value) => value,
+        Err(err) => {
+            println!("{err}");
+            std::process::exit(1);
+        }
+    }
+}
+
+fn negative_cycle_main<W: Weight>(graph: &graph::Graph<W>, verbose: bool) {
+    if verbose {
+        println!("nodes:");
+
+        for (node, neighbors) in graph.neighbors.iter().enumerate() {
+            print!("    {} ->", node + 1);
+
+            for v in neighbors {
+                print!(" {}", v + 1);
+            }
+
+            println!();
+        }
+    }
+
+    let found = check_overflow(bellman_ford::negative_cycle_nodes(graph, verbose));
+
+    if found.is_empty() {
+        return;
+    }
+
+    let output: Vec<String> = found.iter()
+        .map(|node| (node + 1).to_string())
+        .collect();
+
+    println!("{}", output.join(" "));
+}
+
+fn arbitrage_main<I>(lines: &mut I, tolerance: f64, verbose: bool)
+where
+    I: Iterator<Item = std::io::Result<String>>
+{
+    let table = arbitrage::read_rate_table(lines);
+    let found = arbitrage::find_arbitrage(&table, tolerance, verbose);
+
+    if found.is_empty() {
+        println!("no arbitrage found");
+        return;
+    }
+
+    for arb in found {
+        let mut path: Vec<&str> = arb.cycle.iter()
+            .map(|index| table.names[*index].as_str())
+            .collect();
+
+        path.push(&table.names[arb.cycle[0]]);
+
+        println!("arbitrage: {}\nprofit multiplier: {}", path.join(" -> "), arb.profit);
+    }
+}
+
+fn all_pairs_main<W: Weight>(graph: graph::Graph<W>, mode: Mode, verbose: bool) {
+    // the header matches the output of the floyd-warshall reference so that
+    // either engine can be checked against the same test files
+    println!("Enter the number of nodes and the number of edges separated by a space: Enter m edges (u,v) and their weights: ");
+
+    for count in 0..graph.edges.len() {
+        println!("{count}");
+    }
+
+    // the reference overwrites the weight when an edge is given more than
+    // once so the same is done here
+    let graph = graph.without_parallel_edges();
+
+    let result = match mode {
+        Mode::Johnson => all_pairs::johnson(&graph, verbose),
+        Mode::FloydWarshall => all_pairs::floyd_warshall(&graph),
+        _ => unreachable!(),
+    };
+
+    let Some(matrix) = check_overflow(result) else {
+        println!("graph contains a negative cycle");
+        return;
+    };
+
+    println!("The distance matrix for G: ");
+
+    for row in matrix {
+        for dist in row {
+            if let Some(value) = dist {
+                print!("{value} ");
+            } else {
+                print!("INF ");
+            }
+        }
+
+        println!();
+    }
+}
+
+/// creates a space separated list of 1 indexed nodes
+fn format_path(path: &[usize]) -> String {
+    let output: Vec<String> = path.iter()
+        .map(|node| (node + 1).to_string())
+        .collect();
+
+    output.join(" ")
+}
+
+fn sources_main<W: Weight>(graph: &graph::Graph<W>, list: &str) {
+    let mut sources = Vec::new();
+
+    for value in list.split(',') {
+        let Some(source) = graph.parse_node(value) else {
+            panic!("invalid source node provided: \"{}\"", value);
+        };
+
+        sources.push(source);
+    }
+
+    let found = check_overflow(bellman_ford::shortest_paths(graph, &sources));
+
+    for node in 0..graph.len() {
+        print!("{} {}", node + 1, found.dist[node]);
+
+        if let Some(path) = found.path_to(graph, node) {
+            print!(" | {}", format_path(&path));
+        }
+
+        println!();
+    }
+}
+
+fn target_main<W: Weight>(graph: &graph::Graph<W>, value: &str) {
+    let Some(target) = graph.parse_node(value) else {
+        panic!("invalid target node provided: \"{}\"", value);
+    };
+
+    // the shortest path from every node to the target is the same as the
+    // shortest path from the target to every node in the reversed graph
+    let reversed = graph.reverse();
+    let found = check_overflow(bellman_ford::shortest_paths(&reversed, &[target]));
+
+    for node in 0..graph.len() {
+        print!("{} {}", node + 1, found.dist[node]);
+
+        if let Some(mut path) = found.path_to(&reversed, node) {
+            path.reverse();
+
+            print!(" | {}", format_path(&path));
+        }
+
+        println!();
+    }
+}
+
+fn queries_main<W, I>(graph: &graph::Graph<W>, lines: &mut I)
+where
+    W: Weight,
+    I: Iterator<Item = std::io::Result<String>>
+{
+    let mut engine = check_overflow(queries::QueryEngine::new(graph));
+
+    while let Some((source, target)) = read_query(graph, lines) {
+        let found = check_overflow(engine.paths_from(source));
+
+        print!("{} -> {} = {}", source + 1, target + 1, found.dist[target]);
+
+        if let Some(path) = found.path_to(graph, target) {
+            print!(" | {}", format_path(&path));
+        }
+
+        println!();
+    }
+}
+
+/// reads pairs of "{source} {target}" from the remaining lines
+fn read_query<W, I>(graph: &graph::Graph<W>, lines: &mut I) -> Option<(usize, usize)>
+where
+    W: Weight,
+    I: Iterator<Item = std::io::Result<String>>
+{
+    for line in lines {
+        let valid = line.expect("failed to read input from stdin");
+
+        if valid.trim().is_empty() {
+            continue;
+        }
+
+        let Some((source_str, target_str)) = valid.trim().split_once(' ') else {
+            panic!("invalid query provided: \"{}\"", valid);
+        };
+
+        let (Some(source), Some(target)) = (graph.parse_node(source_str), graph.parse_node(target_str)) else {
+            panic!("invalid query nodes provided: \"{}\"", valid);
+        };
+
+        return Some((source, target));
+    }
+
+    None
+}
+
+fn k_shortest_main<W, I>(graph: &graph::Graph<W>, lines: &mut I, k: usize)
+where
+    W: Weight,
+    I: Iterator<Item = std::io::Result<String>>
+{
+    if let Some(edge) = graph.edges.iter().find(|edge| edge.weight < W::ZERO) {
+        panic!("k shortest paths requires non negative edge weights. {} -> {} w: {}", edge.u + 1, edge.v + 1, edge.weight);
+    }
+
+    while let Some((source, target)) = read_query(graph, lines) {
+        println!("{} -> {}", source + 1, target + 1);
+
+        let found = check_overflow(yen::k_shortest_paths(graph, source, target, k));
+
+        if found.is_empty() {
+            println!("no path");
+            continue;
+        }
+
+        // paths through the same nodes with different parallel edges are
+        // told apart by the 1 indexed edges in the order they were given
+        for (index, path) in found.iter().enumerate() {
+            println!("{}: {} | {} | edges {}", index + 1, path.cost, format_path(&path.nodes), format_path(&path.edges));
+        }
+    }
 }
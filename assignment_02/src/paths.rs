@@ -0,0 +1,53 @@
+use crate::graph::Graph;
+use crate::weight::Weight;
+
+/// the shortest distance to a node
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Distance<W> {
+    Unreachable,
+    Finite(W),
+    /// the node can be reached from a negative cycle so there is no
+    /// shortest distance
+    NegativeInfinity,
+}
+
+impl<W: Weight> std::fmt::Display for Distance<W> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Distance::Unreachable => write!(f, "INF"),
+            Distance::Finite(value) => write!(f, "{}", value),
+            Distance::NegativeInfinity => write!(f, "-INF"),
+        }
+    }
+}
+
+/// the result of a shortest path search from one or more sources
+#[derive(Debug)]
+pub struct Paths<W> {
+    pub dist: Vec<Distance<W>>,
+    /// the index of the edge used to reach each node
+    pub prev: Vec<Option<usize>>,
+}
+
+impl<W: Weight> Paths<W> {
+    /// follows the edges used to reach the given node back to the source
+    /// that it came from. the returned list starts with the source. returns
+    /// None if the node does not have a finite distance
+    pub fn path_to(&self, graph: &Graph<W>, node: usize) -> Option<Vec<usize>> {
+        let Distance::Finite(_) = self.dist[node] else {
+            return None;
+        };
+
+        let mut rtn = vec![node];
+        let mut current = node;
+
+        while let Some(edge_index) = self.prev[current] {
+            current = graph.edges[edge_index].u;
+            rtn.push(current);
+        }
+
+        rtn.reverse();
+
+        Some(rtn)
+    }
+}
@@ -0,0 +1,71 @@
+use std::collections::HashMap;
+
+use crate::bellman_ford;
+use crate::dijkstra;
+use crate::graph::Graph;
+use crate::paths::{Distance, Paths};
+use crate::weight::{self, Overflow, Weight};
+
+/// answers shortest path queries against a single loaded graph
+///
+/// the graph is reweighted once with the bellman-ford potentials so that
+/// each source only needs a single run of dijkstra. if the graph contains a
+/// negative cycle then bellman-ford is run for each source instead. results
+/// are cached by source so repeated sources are not calculated again.
+pub struct QueryEngine<'a, W> {
+    graph: &'a Graph<W>,
+    /// the potentials along with the reweighted edges
+    reweighted: Option<(Vec<W>, Vec<W>)>,
+    cache: HashMap<usize, Paths<W>>,
+}
+
+impl<'a, W: Weight> QueryEngine<'a, W> {
+    pub fn new(graph: &'a Graph<W>) -> Result<Self, Overflow> {
+        let reweighted = match bellman_ford::potentials(graph)? {
+            Some(h) => {
+                let edges = bellman_ford::reweight(graph, &h)?;
+
+                Some((h, edges))
+            },
+            None => None,
+        };
+
+        Ok(QueryEngine {
+            graph,
+            reweighted,
+            cache: HashMap::new(),
+        })
+    }
+
+    /// retrieves the shortest paths from the given source
+    pub fn paths_from(&mut self, source: usize) -> Result<&Paths<W>, Overflow> {
+        if !self.cache.contains_key(&source) {
+            let paths = self.calc_paths(source)?;
+
+            self.cache.insert(source, paths);
+        }
+
+        Ok(&self.cache[&source])
+    }
+
+    fn calc_paths(&self, source: usize) -> Result<Paths<W>, Overflow> {
+        let Some((h, edges)) = &self.reweighted else {
+            return bellman_ford::shortest_paths(self.graph, &[source]);
+        };
+
+        let found = dijkstra::shortest_paths(self.graph, source, |index, _| Some(edges[index]))?;
+        let mut dist = Vec::with_capacity(self.graph.len());
+
+        for (target, d) in found.dist.into_iter().enumerate() {
+            dist.push(match d {
+                Some(d) => Distance::Finite(weight::add(weight::sub(d, h[source])?, h[target])?),
+                None => Distance::Unreachable,
+            });
+        }
+
+        Ok(Paths {
+            dist,
+            prev: found.prev,
+        })
+    }
+}
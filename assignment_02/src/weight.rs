@@ -0,0 +1,202 @@
+/// the operations needed from an edge weight by the shortest path
+/// algorithms
+///
+/// every operation is checked so that an overflow is reported instead of
+/// silently wrapping around
+pub trait Weight: Copy + PartialOrd + std::fmt::Debug + std::fmt::Display {
+    const ZERO: Self;
+    /// the weight given to an edge when none is specified
+    const ONE: Self;
+
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+
+    fn checked_sub(self, rhs: Self) -> Option<Self>;
+
+    fn parse_weight(value: &str) -> Option<Self>;
+}
+
+/// an arithmetic operation that did not fit in the weight type
+#[derive(Debug)]
+pub struct Overflow {
+    lhs: String,
+    op: char,
+    rhs: String,
+}
+
+impl std::fmt::Display for Overflow {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "weight overflow: {} {} {}", self.lhs, self.op, self.rhs)
+    }
+}
+
+pub fn add<W: Weight>(lhs: W, rhs: W) -> Result<W, Overflow> {
+    lhs.checked_add(rhs).ok_or_else(|| Overflow {
+        lhs: lhs.to_string(),
+        op: '+',
+        rhs: rhs.to_string(),
+    })
+}
+
+pub fn sub<W: Weight>(lhs: W, rhs: W) -> Result<W, Overflow> {
+    lhs.checked_sub(rhs).ok_or_else(|| Overflow {
+        lhs: lhs.to_string(),
+        op: '-',
+        rhs: rhs.to_string(),
+    })
+}
+
+macro_rules! int_weight {
+    ($t:ty) => {
+        impl Weight for $t {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+
+            fn checked_add(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_add(self, rhs)
+            }
+
+            fn checked_sub(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_sub(self, rhs)
+            }
+
+            fn parse_weight(value: &str) -> Option<Self> {
+                value.parse().ok()
+            }
+        }
+    };
+}
+
+int_weight!(i32);
+int_weight!(i64);
+int_weight!(i128);
+
+/// floating point weights only count as overflowing when the result is no
+/// longer finite
+impl Weight for f64 {
+    const ZERO: Self = 0.0;
+    const ONE: Self = 1.0;
+
+    fn checked_add(self, rhs: Self) -> Option<Self> {
+        let rtn = self + rhs;
+
+        if rtn.is_finite() {
+            Some(rtn)
+        } else {
+            None
+        }
+    }
+
+    fn checked_sub(self, rhs: Self) -> Option<Self> {
+        let rtn = self - rhs;
+
+        if rtn.is_finite() {
+            Some(rtn)
+        } else {
+            None
+        }
+    }
+
+    fn parse_weight(value: &str) -> Option<Self> {
+        let rtn: f64 = value.parse().ok()?;
+
+        if rtn.is_finite() {
+            Some(rtn)
+        } else {
+            None
+        }
+    }
+}
+
+fn gcd(mut a: i128, mut b: i128) -> i128 {
+    a = a.abs();
+    b = b.abs();
+
+    while b != 0 {
+        let t = a % b;
+        a = b;
+        b = t;
+    }
+
+    a
+}
+
+/// an exact fraction of two i64 values
+///
+/// the fraction is always reduced with a positive denominator. calculations
+/// are done with i128 and fail if the reduced result does not fit back into
+/// i64
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rational {
+    num: i64,
+    den: i64,
+}
+
+impl Rational {
+    fn reduce(num: i128, den: i128) -> Option<Self> {
+        if den == 0 {
+            return None;
+        }
+
+        let divisor = gcd(num, den);
+        let sign = if den < 0 { -1 } else { 1 };
+
+        Some(Rational {
+            num: (sign * num / divisor).try_into().ok()?,
+            den: (sign * den / divisor).try_into().ok()?,
+        })
+    }
+}
+
+impl std::fmt::Display for Rational {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.den == 1 {
+            write!(f, "{}", self.num)
+        } else {
+            write!(f, "{}/{}", self.num, self.den)
+        }
+    }
+}
+
+impl PartialOrd for Rational {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Rational {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        let lhs = self.num as i128 * other.den as i128;
+        let rhs = other.num as i128 * self.den as i128;
+
+        lhs.cmp(&rhs)
+    }
+}
+
+impl Weight for Rational {
+    const ZERO: Self = Rational { num: 0, den: 1 };
+    const ONE: Self = Rational { num: 1, den: 1 };
+
+    fn checked_add(self, rhs: Self) -> Option<Self> {
+        let num = self.num as i128 * rhs.den as i128 + rhs.num as i128 * self.den as i128;
+        let den = self.den as i128 * rhs.den as i128;
+
+        Rational::reduce(num, den)
+    }
+
+    fn checked_sub(self, rhs: Self) -> Option<Self> {
+        let num = self.num as i128 * rhs.den as i128 - rhs.num as i128 * self.den as i128;
+        let den = self.den as i128 * rhs.den as i128;
+
+        Rational::reduce(num, den)
+    }
+
+    /// accepts either "{num}/{den}" or "{num}"
+    fn parse_weight(value: &str) -> Option<Self> {
+        let (num, den) = match value.split_once('/') {
+            Some((num, den)) => (num.parse::<i64>().ok()?, den.parse::<i64>().ok()?),
+            None => (value.parse::<i64>().ok()?, 1),
+        };
+
+        Rational::reduce(num as i128, den as i128)
+    }
+}
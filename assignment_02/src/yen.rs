@@ -0,0 +1,153 @@
+use std::collections::HashSet;
+
+use crate::dijkstra;
+use crate::graph::Graph;
+use crate::weight::{self, Overflow, Weight};
+
+/// a single path through the graph
+#[derive(Debug, Clone)]
+pub struct Path<W> {
+    pub cost: W,
+    /// the nodes visited starting with the source and ending with the target
+    pub nodes: Vec<usize>,
+    /// the index of each edge taken
+    pub edges: Vec<usize>,
+}
+
+/// follows the previous edges from the target back to the source
+fn collect_edges<W: Weight>(graph: &Graph<W>, prev: &[Option<usize>], target: usize) -> Vec<usize> {
+    let mut rtn = Vec::new();
+    let mut current = target;
+
+    while let Some(edge_index) = prev[current] {
+        rtn.push(edge_index);
+        current = graph.edges[edge_index].u;
+    }
+
+    rtn.reverse();
+    rtn
+}
+
+/// finds up to k shortest simple paths from source to target using yen's
+/// algorithm
+///
+/// the first path is found with dijkstra. each following path is found by
+/// taking every node of the previous path as a spur node, removing the
+/// edges that would recreate an already found path along with the nodes of
+/// the root path and then finding the shortest path from the spur node to
+/// the target. the cheapest candidate becomes the next path. paths are
+/// returned in order of cost with ties broken by the nodes visited and then
+/// the edges taken. all edge weights are expected to be non negative.
+///
+/// a path is the list of edges it takes, not the nodes it visits, so
+/// parallel edges between two nodes give different paths through the same
+/// nodes. no node is visited more than once.
+pub fn k_shortest_paths<W: Weight>(graph: &Graph<W>, source: usize, target: usize, k: usize) -> Result<Vec<Path<W>>, Overflow> {
+    let mut found: Vec<Path<W>> = Vec::new();
+
+    if k == 0 {
+        return Ok(found);
+    }
+
+    let first = dijkstra::shortest_paths(graph, source, |_, edge| Some(edge.weight))?;
+
+    let Some(cost) = first.dist[target] else {
+        return Ok(found);
+    };
+
+    let edges = collect_edges(graph, &first.prev, target);
+    let mut nodes = vec![source];
+    nodes.extend(edges.iter().map(|edge_index| graph.edges[*edge_index].v));
+
+    found.push(Path {
+        cost,
+        nodes,
+        edges,
+    });
+
+    let mut candidates: Vec<Path<W>> = Vec::new();
+    let mut known: HashSet<Vec<usize>> = HashSet::new();
+    known.insert(found[0].edges.clone());
+
+    while found.len() < k {
+        let last = found.last().unwrap().clone();
+
+        for spur_index in 0..last.edges.len() {
+            let spur_node = last.nodes[spur_index];
+            let root_edges = &last.edges[..spur_index];
+
+            let mut removed_edges = HashSet::new();
+
+            // roots are compared by edges since two paths can visit the same
+            // nodes through different parallel edges
+            for path in &found {
+                if path.edges.len() > spur_index && path.edges[..spur_index] == *root_edges {
+                    removed_edges.insert(path.edges[spur_index]);
+                }
+            }
+
+            // the spur node is kept since the spur path starts from it
+            let removed_nodes: HashSet<usize> = last.nodes[..spur_index].iter()
+                .copied()
+                .collect();
+
+            let spur = dijkstra::shortest_paths(graph, spur_node, |edge_index, edge| {
+                if removed_edges.contains(&edge_index) ||
+                    removed_nodes.contains(&edge.u) ||
+                    removed_nodes.contains(&edge.v) {
+                    None
+                } else {
+                    Some(edge.weight)
+                }
+            })?;
+
+            let Some(spur_cost) = spur.dist[target] else {
+                continue;
+            };
+
+            let spur_edges = collect_edges(graph, &spur.prev, target);
+            let mut edges = root_edges.to_vec();
+            edges.extend(spur_edges);
+
+            if known.contains(&edges) {
+                continue;
+            }
+
+            let mut cost = spur_cost;
+
+            for edge_index in root_edges {
+                cost = weight::add(cost, graph.edges[*edge_index].weight)?;
+            }
+
+            let mut nodes = vec![source];
+            nodes.extend(edges.iter().map(|edge_index| graph.edges[*edge_index].v));
+
+            known.insert(edges.clone());
+            candidates.push(Path {
+                cost,
+                nodes,
+                edges,
+            });
+        }
+
+        // weights are only partially ordered so the cheapest candidate is
+        // searched for instead of keeping them in a heap
+        let cheapest = candidates.iter()
+            .enumerate()
+            .min_by(|(_, a), (_, b)| {
+                a.cost.partial_cmp(&b.cost)
+                    .unwrap_or(std::cmp::Ordering::Equal)
+                    .then_with(|| a.nodes.cmp(&b.nodes))
+                    .then_with(|| a.edges.cmp(&b.edges))
+            })
+            .map(|(index, _)| index);
+
+        let Some(index) = cheapest else {
+            break;
+        };
+
+        found.push(candidates.swap_remove(index));
+    }
+
+    Ok(found)
+}
@@ -0,0 +1,113 @@
+use std::collections::VecDeque;
+
+use crate::Change;
+
+/// marks an amount that can not be made
+const INVALID: u32 = u32::MAX;
+
+/// the minimum change for every amount up to a max size when there is only
+/// a limited stock of each denomination
+///
+/// every value is stored as u32 the same as the change table
+pub struct BoundedChange {
+    /// the fewest coins needed for each amount
+    best: Vec<u32>,
+    /// `used[i][amount]` is the amount of denomination i used in the best
+    /// change for the amount considering denominations 0 to i
+    used: Vec<Vec<u32>>,
+}
+
+impl BoundedChange {
+    /// follows the amounts used of each denomination back from the last one
+    pub fn change_for(&self, dnmn: &[usize], value: usize) -> Option<Change> {
+        if self.best[value] == INVALID {
+            return None;
+        }
+
+        let mut rtn = Change::new(dnmn.len());
+        let mut amount = value;
+
+        for index in (0..dnmn.len()).rev() {
+            let count = self.used[index][amount] as usize;
+
+            rtn.amounts[index] = count;
+            amount -= count * dnmn[index];
+        }
+
+        rtn.total = self.best[value] as usize;
+
+        Some(rtn)
+    }
+}
+
+/// calculates the minimum change for every amount up to max_size where
+/// denomination i can be used at most stock[i] times
+///
+/// each denomination is added one at a time. an amount can only be built
+/// from amounts that share the same remainder when divided by the
+/// denomination, so every remainder is walked separately with a sliding
+/// window over the last stock + 1 amounts. a monotonic queue keeps the
+/// window minimum which makes each denomination O(max_size).
+pub fn calc_bounded(dnmn: &[usize], stock: &[usize], max_size: usize) -> BoundedChange {
+    if max_size >= INVALID as usize {
+        panic!("max size is too large for the bounded table: {max_size}");
+    }
+
+    let mut best = vec![INVALID; max_size + 1];
+    let mut used = vec![vec![0; max_size + 1]; dnmn.len()];
+    best[0] = 0;
+
+    // holds (k, coins) for the amount remainder + k * value. every amount
+    // is read in to the window before its best change is replaced, so best
+    // is updated in place instead of keeping a copy from before the
+    // denomination was added
+    let mut window: VecDeque<(u32, u32)> = VecDeque::new();
+
+    for (index, (value, limit)) in dnmn.iter().zip(stock).enumerate() {
+        if *value == 0 || *limit == 0 {
+            continue;
+        }
+
+        for remainder in 0..(*value).min(max_size + 1) {
+            let mut k = 0;
+            let mut amount = remainder;
+
+            window.clear();
+
+            while amount <= max_size {
+                // subtracting k from the coins of every entry makes adding the
+                // coins needed to get to a later amount the same for all of them
+                let adjusted = |(start, coins): (u32, u32)| coins as i64 - start as i64;
+
+                if best[amount] != INVALID {
+                    let entry = (k, best[amount]);
+
+                    while window.back().is_some_and(|back| adjusted(*back) >= adjusted(entry)) {
+                        window.pop_back();
+                    }
+
+                    window.push_back(entry);
+                }
+
+                while window.front().is_some_and(|(front, _)| (*front as usize).saturating_add(*limit) < k as usize) {
+                    window.pop_front();
+                }
+
+                if let Some(front) = window.front() {
+                    best[amount] = (adjusted(*front) + k as i64) as u32;
+                    used[index][amount] = k - front.0;
+                } else {
+                    best[amount] = INVALID;
+                }
+
+                k += 1;
+                amount += value;
+            }
+        }
+    }
+
+    BoundedChange {
+        best,
+        used,
+    }
+}
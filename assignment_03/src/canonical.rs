@@ -0,0 +1,99 @@
+use crate::Change;
+use crate::table::ChangeTable;
+
+/// an amount where greedy change uses more coins than the optimal change or
+/// can not make the amount at all
+pub struct Counterexample {
+    pub amount: usize,
+    /// None if the greedy choices leave an amount that can not be made
+    pub greedy: Option<Change>,
+    pub optimal: Change,
+}
+
+/// the result of checking if greedy change is always optimal
+pub enum Check {
+    Canonical,
+    Counterexample(Counterexample),
+    /// no counterexample was found below the given amount but without a
+    /// denomination of 1 there could still be one above it
+    NoneBelow(usize),
+}
+
+/// makes change by always taking the largest denomination that fits
+///
+/// returns None if the greedy choices leave an amount that can not be made
+pub fn greedy_change(dnmn: &[usize], value: usize) -> Option<Change> {
+    let mut order: Vec<usize> = (0..dnmn.len()).collect();
+    order.sort_by(|a, b| dnmn[*b].cmp(&dnmn[*a]));
+
+    let mut rtn = Change::new(dnmn.len());
+    let mut remaining = value;
+
+    for index in order {
+        if dnmn[index] == 0 {
+            continue;
+        }
+
+        let count = remaining / dnmn[index];
+
+        rtn.amounts[index] = count;
+        rtn.total += count;
+        remaining -= count * dnmn[index];
+    }
+
+    if remaining == 0 {
+        Some(rtn)
+    } else {
+        None
+    }
+}
+
+/// finds the smallest amount where greedy change is not optimal
+///
+/// kozen and zaks showed that if a counterexample exists for a set
+/// containing 1 then the smallest one is below the sum of the two largest
+/// denominations so only amounts below that are checked against the
+/// optimal change from `calc_bottom_up`. without 1 the same amounts are
+/// checked, skipping any that can not be made, but finding nothing does not
+/// prove that the denominations are canonical.
+pub fn check(dnmn: &[usize]) -> Check {
+    let mut sorted = dnmn.to_vec();
+    sorted.sort_unstable_by(|a, b| b.cmp(a));
+
+    let has_one = dnmn.contains(&1);
+
+    // greedy change is always optimal with a single denomination and any
+    // set of 2 denominations that contains 1 is canonical
+    if sorted.len() < 2 || (has_one && sorted.len() < 3) {
+        return Check::Canonical;
+    }
+
+    let bound = sorted[0] + sorted[1];
+    let mut table = ChangeTable::new(bound - 1);
+
+    crate::calc_bottom_up(dnmn, &mut table);
+
+    for amount in 1..bound {
+        let Some(optimal_total) = table.coins(amount) else {
+            continue;
+        };
+
+        let greedy = greedy_change(dnmn, amount);
+
+        if greedy.as_ref().is_some_and(|greedy| greedy.total <= optimal_total) {
+            continue;
+        }
+
+        return Check::Counterexample(Counterexample {
+            amount,
+            greedy,
+            optimal: table.change_for(dnmn, amount).unwrap(),
+        });
+    }
+
+    if has_one {
+        Check::Canonical
+    } else {
+        Check::NoneBelow(bound)
+    }
+}
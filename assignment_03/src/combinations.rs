@@ -0,0 +1,230 @@
+/// builds a table of which amounts up to target can be made with each suffix
+/// of the denominations
+///
+/// `table[i][amount]` is true if the amount can be made using only the
+/// denominations from i onwards. `table[dnmn.len()]` is only true for 0
+fn suffix_reachable(dnmn: &[usize], target: usize) -> Vec<Vec<bool>> {
+    let mut table = vec![vec![false; target + 1]; dnmn.len() + 1];
+    table[dnmn.len()][0] = true;
+
+    for (index, value) in dnmn.iter().enumerate().rev() {
+        for amount in 0..=target {
+            table[index][amount] = table[index + 1][amount] ||
+                (*value != 0 && amount >= *value && table[index][amount - *value]);
+        }
+    }
+
+    table
+}
+
+/// lazily yields every distinct combination of coins that adds up to a
+/// target as the amount used of each denomination
+///
+/// combinations are produced by using as many of the earlier denominations
+/// as possible first and then taking one less each step. a table of which
+/// amounts can be reached by the remaining denominations is used so that
+/// every step ends in a valid combination instead of searching dead ends.
+/// denominations of 0 are never used.
+pub struct Combinations<'a> {
+    dnmn: &'a [usize],
+    reachable: Vec<Vec<bool>>,
+    counts: Vec<usize>,
+    /// the amount left to make before picking the count at each index
+    remaining: Vec<usize>,
+    started: bool,
+    done: bool,
+}
+
+impl<'a> Combinations<'a> {
+    pub fn new(dnmn: &'a [usize], target: usize) -> Self {
+        let mut remaining = vec![0; dnmn.len() + 1];
+        remaining[0] = target;
+
+        Combinations {
+            dnmn,
+            reachable: suffix_reachable(dnmn, target),
+            counts: vec![0; dnmn.len()],
+            remaining,
+            started: false,
+            done: false,
+        }
+    }
+
+    /// sets the count at index and updates the amount left for the next
+    /// denomination
+    fn set_count(&mut self, index: usize, count: usize) {
+        self.counts[index] = count;
+        self.remaining[index + 1] = self.remaining[index] - count * self.dnmn[index];
+    }
+
+    /// finds the largest count below or equal to max for the given index
+    /// that leaves an amount the following denominations are able to make
+    fn largest_count(&self, index: usize, max: usize) -> Option<usize> {
+        let value = self.dnmn[index];
+        let amount = self.remaining[index];
+
+        if value == 0 {
+            return if self.reachable[index + 1][amount] { Some(0) } else { None };
+        }
+
+        let mut count = max.min(amount / value);
+
+        loop {
+            if self.reachable[index + 1][amount - count * value] {
+                return Some(count);
+            }
+
+            if count == 0 {
+                return None;
+            }
+
+            count -= 1;
+        }
+    }
+
+    /// fills every index from the given one onwards with the largest count
+    /// possible
+    fn fill_from(&mut self, start: usize) {
+        for index in start..self.dnmn.len() {
+            let count = self.largest_count(index, usize::MAX)
+                .expect("remaining amount should be reachable");
+
+            self.set_count(index, count);
+        }
+    }
+}
+
+impl<'a> Iterator for Combinations<'a> {
+    type Item = Vec<usize>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        if !self.started {
+            self.started = true;
+
+            if !self.reachable[0][self.remaining[0]] {
+                self.done = true;
+                return None;
+            }
+
+            self.fill_from(0);
+
+            return Some(self.counts.clone());
+        }
+
+        // the last denomination is decided by the ones before it so the
+        // search for a count to lower starts one before it
+        for index in (0..self.dnmn.len().saturating_sub(1)).rev() {
+            if self.counts[index] == 0 {
+                continue;
+            }
+
+            if let Some(count) = self.largest_count(index, self.counts[index] - 1) {
+                self.set_count(index, count);
+                self.fill_from(index + 1);
+
+                return Some(self.counts.clone());
+            }
+        }
+
+        self.done = true;
+
+        None
+    }
+}
+
+/// lazily yields every ordered sequence of coins that adds up to a target as
+/// the index of each denomination used
+///
+/// sequences are produced in lexicographic order of the indices. only coins
+/// that leave a reachable amount are picked so every step ends in a valid
+/// sequence. denominations of 0 are never used.
+pub struct Compositions<'a> {
+    dnmn: &'a [usize],
+    reachable: Vec<bool>,
+    sequence: Vec<usize>,
+    remaining: usize,
+    started: bool,
+    done: bool,
+}
+
+impl<'a> Compositions<'a> {
+    pub fn new(dnmn: &'a [usize], target: usize) -> Self {
+        let reachable = suffix_reachable(dnmn, target).swap_remove(0);
+
+        Compositions {
+            dnmn,
+            reachable,
+            sequence: Vec::new(),
+            remaining: target,
+            started: false,
+            done: false,
+        }
+    }
+
+    /// finds the first denomination from start onwards that leaves a
+    /// reachable amount
+    fn next_coin(&self, start: usize) -> Option<usize> {
+        (start..self.dnmn.len()).find(|index| {
+            let value = self.dnmn[*index];
+
+            value != 0 && value <= self.remaining && self.reachable[self.remaining - value]
+        })
+    }
+
+    fn push(&mut self, index: usize) {
+        self.sequence.push(index);
+        self.remaining -= self.dnmn[index];
+    }
+
+    /// keeps adding the first valid coin until the target is reached
+    fn extend(&mut self) {
+        while self.remaining > 0 {
+            let index = self.next_coin(0)
+                .expect("remaining amount should be reachable");
+
+            self.push(index);
+        }
+    }
+}
+
+impl<'a> Iterator for Compositions<'a> {
+    type Item = Vec<usize>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        if !self.started {
+            self.started = true;
+
+            if !self.reachable[self.remaining] {
+                self.done = true;
+                return None;
+            }
+
+            self.extend();
+
+            return Some(self.sequence.clone());
+        }
+
+        while let Some(last) = self.sequence.pop() {
+            self.remaining += self.dnmn[last];
+
+            if let Some(index) = self.next_coin(last + 1) {
+                self.push(index);
+                self.extend();
+
+                return Some(self.sequence.clone());
+            }
+        }
+
+        self.done = true;
+
+        None
+    }
+}
@@ -0,0 +1,191 @@
+/// the operations needed to count the ways of making change
+pub trait Count: Clone + std::fmt::Display {
+    fn add(&mut self, other: &Self);
+}
+
+/// an unsigned integer of any size
+///
+/// the value is stored as base 2^64 limbs with the least significant limb
+/// first. only addition is needed for counting so nothing else is provided
+#[derive(Debug, Clone)]
+pub struct BigUint {
+    limbs: Vec<u64>,
+}
+
+/// the largest power of 10 that fits in a u64, used when converting to
+/// decimal
+const DECIMAL_BASE: u64 = 10_000_000_000_000_000_000;
+const DECIMAL_DIGITS: usize = 19;
+
+impl BigUint {
+    pub fn zero() -> Self {
+        BigUint {
+            limbs: Vec::new(),
+        }
+    }
+
+    pub fn one() -> Self {
+        BigUint {
+            limbs: vec![1],
+        }
+    }
+
+    /// divides the value by a single limb, returning the quotient and the
+    /// remainder
+    fn div_rem_limb(&self, divisor: u64) -> (BigUint, u64) {
+        let mut limbs = vec![0; self.limbs.len()];
+        let mut rem: u128 = 0;
+
+        for (index, limb) in self.limbs.iter().enumerate().rev() {
+            let current = (rem << 64) | *limb as u128;
+
+            limbs[index] = (current / divisor as u128) as u64;
+            rem = current % divisor as u128;
+        }
+
+        while limbs.last() == Some(&0) {
+            limbs.pop();
+        }
+
+        (BigUint { limbs }, rem as u64)
+    }
+}
+
+impl Count for BigUint {
+    fn add(&mut self, other: &Self) {
+        if self.limbs.len() < other.limbs.len() {
+            self.limbs.resize(other.limbs.len(), 0);
+        }
+
+        let mut carry = false;
+
+        for (index, limb) in self.limbs.iter_mut().enumerate() {
+            // nothing is left to add once the other value and the carry are
+            // used up
+            if index >= other.limbs.len() && !carry {
+                break;
+            }
+
+            let rhs = other.limbs.get(index).copied().unwrap_or(0);
+
+            let (sum, first) = limb.overflowing_add(rhs);
+            let (sum, second) = sum.overflowing_add(carry as u64);
+
+            *limb = sum;
+            carry = first || second;
+        }
+
+        if carry {
+            self.limbs.push(1);
+        }
+    }
+}
+
+impl std::fmt::Display for BigUint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.limbs.is_empty() {
+            return write!(f, "0");
+        }
+
+        let mut chunks = Vec::new();
+        let mut current = self.clone();
+
+        while !current.limbs.is_empty() {
+            let (quotient, rem) = current.div_rem_limb(DECIMAL_BASE);
+
+            chunks.push(rem);
+            current = quotient;
+        }
+
+        write!(f, "{}", chunks.last().unwrap())?;
+
+        for chunk in chunks.iter().rev().skip(1) {
+            write!(f, "{:0width$}", chunk, width = DECIMAL_DIGITS)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// a count that is kept modulo a given value
+#[derive(Debug, Clone)]
+pub struct Modular {
+    value: u64,
+    modulus: u64,
+}
+
+impl Modular {
+    pub fn zero(modulus: u64) -> Self {
+        Modular {
+            value: 0,
+            modulus,
+        }
+    }
+
+    pub fn one(modulus: u64) -> Self {
+        Modular {
+            value: 1 % modulus,
+            modulus,
+        }
+    }
+}
+
+impl Count for Modular {
+    fn add(&mut self, other: &Self) {
+        self.value = ((self.value as u128 + other.value as u128) % self.modulus as u128) as u64;
+    }
+}
+
+impl std::fmt::Display for Modular {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.value)
+    }
+}
+
+/// counts the distinct multisets of coins that add up to every amount from
+/// 0 to max_size
+///
+/// the denominations are the outer loop so each multiset is only counted
+/// once regardless of the order that the coins are picked in
+pub fn count_combinations<C: Count>(dnmn: &[usize], max_size: usize, zero: C, one: C) -> Vec<C> {
+    let mut ways = vec![zero; max_size + 1];
+    ways[0] = one;
+
+    for value in dnmn {
+        if *value == 0 {
+            continue;
+        }
+
+        for amount in *value..=max_size {
+            let (lower, upper) = ways.split_at_mut(amount);
+
+            upper[0].add(&lower[amount - *value]);
+        }
+    }
+
+    ways
+}
+
+/// counts the ordered sequences of coins that add up to every amount from 0
+/// to max_size
+///
+/// the amounts are the outer loop so the same coins picked in a different
+/// order are counted separately
+pub fn count_compositions<C: Count>(dnmn: &[usize], max_size: usize, zero: C, one: C) -> Vec<C> {
+    let mut ways = vec![zero; max_size + 1];
+    ways[0] = one;
+
+    for amount in 1..=max_size {
+        let (lower, upper) = ways.split_at_mut(amount);
+
+        for value in dnmn {
+            if *value == 0 || *value > amount {
+                continue;
+            }
+
+            upper[0].add(&lower[amount - *value]);
+        }
+    }
+
+    ways
+}
@@ -0,0 +1,107 @@
+/// a problem found with the denominations or checks provided
+#[derive(Debug)]
+pub enum InputError {
+    /// a denomination of 0 never changes the amount left so any amount
+    /// could use an infinite amount of them
+    ZeroDenomination {
+        position: usize,
+    },
+    /// the same denomination given twice makes the amount used of each one
+    /// ambiguous
+    DuplicateDenomination {
+        value: usize,
+        first: usize,
+        second: usize,
+    },
+    /// a check of 0 always needs no coins and is most likely a mistake in
+    /// the input
+    ZeroCheck {
+        position: usize,
+    },
+}
+
+impl std::fmt::Display for InputError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputError::ZeroDenomination { position } => {
+                write!(f, "denomination {position} is 0")
+            },
+            InputError::DuplicateDenomination { value, first, second } => {
+                write!(f, "denomination {value} is given more than once. positions: {first} and {second}")
+            },
+            InputError::ZeroCheck { position } => {
+                write!(f, "check {position} is 0")
+            },
+        }
+    }
+}
+
+/// the denominations sorted in ascending order along with the position each
+/// one was given in
+///
+/// the methods are free to assume that the denominations are sorted, non
+/// zero and distinct. anything listed per denomination is converted between
+/// the sorted order and the order of the input so that the output matches
+/// the input. positions in errors start from 1.
+pub struct Denominations {
+    pub sorted: Vec<usize>,
+    /// `positions[i]` is the index in the input of `sorted[i]`
+    positions: Vec<usize>,
+}
+
+impl Denominations {
+    pub fn new(input: &[usize]) -> Result<Self, InputError> {
+        if let Some(index) = input.iter().position(|value| *value == 0) {
+            return Err(InputError::ZeroDenomination {
+                position: index + 1,
+            });
+        }
+
+        let mut positions: Vec<usize> = (0..input.len()).collect();
+        positions.sort_by_key(|index| (input[*index], *index));
+
+        for pair in positions.windows(2) {
+            if input[pair[0]] == input[pair[1]] {
+                return Err(InputError::DuplicateDenomination {
+                    value: input[pair[0]],
+                    first: pair[0] + 1,
+                    second: pair[1] + 1,
+                });
+            }
+        }
+
+        Ok(Denominations {
+            sorted: positions.iter().map(|index| input[*index]).collect(),
+            positions,
+        })
+    }
+
+    /// reorders values given in the order of the input to the sorted order
+    pub fn to_sorted(&self, values: &[usize]) -> Vec<usize> {
+        self.positions.iter()
+            .map(|index| values[*index])
+            .collect()
+    }
+
+    /// reorders values given in the sorted order back to the order of the
+    /// input
+    pub fn to_input(&self, values: &[usize]) -> Vec<usize> {
+        let mut rtn = vec![0; values.len()];
+
+        for (value, index) in values.iter().zip(&self.positions) {
+            rtn[*index] = *value;
+        }
+
+        rtn
+    }
+}
+
+pub fn validate_checks(checks: &[usize]) -> Result<(), InputError> {
+    if let Some(index) = checks.iter().position(|value| *value == 0) {
+        return Err(InputError::ZeroCheck {
+            position: index + 1,
+        });
+    }
+
+    Ok(())
+}
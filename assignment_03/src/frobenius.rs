@@ -0,0 +1,100 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+fn gcd(mut a: usize, mut b: usize) -> usize {
+    while b != 0 {
+        let t = a % b;
+        a = b;
+        b = t;
+    }
+
+    a
+}
+
+/// calculates the greatest common divisor of every denomination, 0 if there
+/// are no denominations above 0
+pub fn gcd_all(dnmn: &[usize]) -> usize {
+    dnmn.iter().fold(0, |acc, value| gcd(acc, *value))
+}
+
+/// the smallest amount that can be made for every remainder when divided by
+/// the smallest denomination
+pub struct Residues {
+    modulus: usize,
+    dist: Vec<usize>,
+}
+
+impl Residues {
+    /// the largest amount that can not be made, None if every amount can be
+    /// made
+    ///
+    /// once the smallest amount for a remainder is found every amount after
+    /// it with the same remainder is made by adding the smallest
+    /// denomination, so the largest unreachable amount is one step below the
+    /// largest of the smallest amounts
+    pub fn frobenius_number(&self) -> Option<usize> {
+        let largest = self.dist.iter().max().copied().unwrap_or(0);
+
+        largest.checked_sub(self.modulus)
+    }
+
+    /// lists every amount that can not be made in ascending order
+    pub fn unreachable_amounts(&self) -> Vec<usize> {
+        let mut rtn = Vec::new();
+
+        for (remainder, smallest) in self.dist.iter().enumerate() {
+            rtn.extend((remainder..*smallest).step_by(self.modulus));
+        }
+
+        rtn.sort_unstable();
+        rtn
+    }
+}
+
+/// finds the smallest amount that can be made for every remainder of the
+/// smallest denomination
+///
+/// each remainder is a node and each denomination d is an edge from r to
+/// (r + d) % smallest with a weight of d, so the shortest path from 0 to a
+/// remainder is the smallest amount that has it. this only needs
+/// O(smallest) nodes instead of scanning every amount up to the frobenius
+/// number. the denominations are expected to be coprime, otherwise some
+/// remainders are never reached.
+pub fn calc_residues(dnmn: &[usize]) -> Residues {
+    let modulus = dnmn.iter()
+        .filter(|value| **value != 0)
+        .min()
+        .copied()
+        .expect("at least one denomination above 0 is required");
+
+    let mut dist = vec![usize::MAX; modulus];
+    let mut heap = BinaryHeap::new();
+
+    dist[0] = 0;
+    heap.push(Reverse((0, 0)));
+
+    while let Some(Reverse((current, remainder))) = heap.pop() {
+        if current > dist[remainder] {
+            continue;
+        }
+
+        for value in dnmn {
+            if *value == 0 {
+                continue;
+            }
+
+            let check = current + value;
+            let next = check % modulus;
+
+            if check < dist[next] {
+                dist[next] = check;
+                heap.push(Reverse((check, next)));
+            }
+        }
+    }
+
+    Residues {
+        modulus,
+        dist,
+    }
+}
@@ -1,5 +1,16 @@
+mod count;
+mod combinations;
+mod bounded;
+mod canonical;
+mod frobenius;
+mod weighted;
+mod table;
+mod denominations;
+
 use std::str::FromStr;
 
+use table::{ChangeTable, Slot};
+
 #[derive(Debug, Clone)]
 struct Change {
     total: usize,
@@ -17,28 +28,6 @@ impl Change {
     }
 }
 
-#[derive(Debug, Clone)]
-enum State {
-    Set(Change),
-    Unset,
-    Invalid,
-}
-
-impl State {
-    fn ref_change(&self) -> Option<&Change> {
-        match self {
-            State::Set(v) => Some(&v),
-            _ => None
-        }
-    }
-}
-
-impl Default for State {
-    fn default() -> Self {
-        State::Unset
-    }
-}
-
 fn parse_line_fill<T>(line: &str, list: &mut Vec<T>) -> bool
 where
     T: FromStr
@@ -73,36 +62,187 @@ where
     }
 }
 
+/// reads a line with a value for each denomination
+fn read_per_denomination<I>(lines: &mut I, name: &str, total_denominations: usize) -> Vec<usize>
+where
+    I: Iterator<Item = std::io::Result<String>>
+{
+    let Some(check) = lines.next() else {
+        panic!("no {name} specified");
+    };
+
+    let line = check.expect("failed to read input from stdin");
+
+    let Some(rtn) = parse_line::<usize>(&line) else {
+        panic!("invalid {name} line provided: \"{}\"", line);
+    };
+
+    if rtn.len() != total_denominations {
+        panic!("{name} provided does not match the specified amount. expected: {total_denominations} given: {}", rtn.len());
+    }
+
+    rtn
+}
+
+/// the different methods that the program can run
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Method {
+    BottomUp,
+    BottomUpAlt,
+    TopDown,
+    BottomUpUnique,
+    Count,
+    List,
+    Bounded,
+    Canonical,
+    Frobenius,
+    Weighted,
+    /// runs every minimum change method and checks that they agree
+    All,
+}
+
+impl Method {
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "bottom-up" => Some(Method::BottomUp),
+            "bottom-up-2" => Some(Method::BottomUpAlt),
+            "top-down" => Some(Method::TopDown),
+            "bottom-up-unique" => Some(Method::BottomUpUnique),
+            "count" => Some(Method::Count),
+            "list" => Some(Method::List),
+            "bounded" => Some(Method::Bounded),
+            "canonical" => Some(Method::Canonical),
+            "frobenius" => Some(Method::Frobenius),
+            "weighted" => Some(Method::Weighted),
+            "all" => Some(Method::All),
+            _ => None,
+        }
+    }
+}
+
+/// the methods that calculate the minimum change and are compared by
+/// `Method::All`
+const MINIMUM_CHANGE_METHODS: [Method; 3] = [Method::BottomUp, Method::BottomUpAlt, Method::TopDown];
+
+/// calculates the minimum change for every check with one of the minimum
+/// change methods. the returned changes are in ascending order of the
+/// denominations, None if the check can not be made
+fn calc_minimum_change(method: Method, denominations: &[usize], checks: &[usize], max_size: usize, verbose: bool) -> Vec<Option<Change>> {
+    let mut table = ChangeTable::new(max_size);
+
+    match method {
+        Method::BottomUp => {
+            calc_bottom_up(denominations, &mut table);
+
+            checks.iter()
+                .map(|value| table.change_for(denominations, *value))
+                .collect()
+        },
+        Method::BottomUpAlt => {
+            calc_bottom_up_alt(denominations, &mut table, verbose);
+
+            checks.iter()
+                .map(|value| table.change_for(denominations, *value))
+                .collect()
+        },
+        Method::TopDown => {
+            // the top down method prefers the larger denominations so it is
+            // given them in descending order
+            let descending: Vec<usize> = denominations.iter().rev().copied().collect();
+
+            checks.iter()
+                .map(|value| {
+                    calc_top_down(*value, &descending, &mut table, verbose);
+
+                    table.change_for(&descending, *value).map(|mut change| {
+                        change.amounts.reverse();
+                        change
+                    })
+                })
+                .collect()
+        },
+        _ => unreachable!(),
+    }
+}
+
 fn main() {
-    let mut method_to_use = 0;
+    let mut method = Method::BottomUp;
     let mut run_checks = false;
-
-    for arg in std::env::args() {
+    let mut verbose = false;
+    let mut ordered = false;
+    let mut modulus: Option<u64> = None;
+    let mut limit: Option<usize> = None;
+    let mut args = std::env::args();
+    args.next();
+
+    while let Some(arg) = args.next() {
         match arg.as_str() {
             "--run-checks" => {
                 run_checks = true;
             },
-            "--bottom-up-2" => {
-                method_to_use = 1;
-            }
-            "--top-down" => {
-                method_to_use = 2;
-            }
-            "--bottom-up-unique" => {
-                method_to_use = 3;
+            "--verbose" => {
+                verbose = true;
+            },
+            "--method" => {
+                let value = args.next()
+                    .expect("missing value for --method");
+
+                let Some(parsed) = Method::parse(&value) else {
+                    panic!("unknown method: \"{}\". expected bottom-up, bottom-up-2, top-down, bottom-up-unique, count, list, bounded, canonical, frobenius, weighted or all", value);
+                };
+
+                method = parsed;
+            },
+            "--limit" => {
+                let value = args.next()
+                    .expect("missing value for --limit");
+
+                let Ok(parsed) = value.parse::<usize>() else {
+                    panic!("invalid limit provided: \"{}\"", value);
+                };
+
+                limit = Some(parsed);
+            },
+            "--ordered" => {
+                ordered = true;
+            },
+            "--modulo" => {
+                let value = args.next()
+                    .expect("missing value for --modulo");
+
+                let Ok(parsed) = value.parse::<u64>() else {
+                    panic!("invalid modulo provided: \"{}\"", value);
+                };
+
+                if parsed == 0 {
+                    panic!("modulo must be greater than 0");
+                }
+
+                modulus = Some(parsed);
+            },
+            _ => {
+                // the older flags are kept as shorthands for --method
+                let Some(parsed) = arg.strip_prefix("--").and_then(Method::parse) else {
+                    panic!("unknown argument: \"{}\"", arg);
+                };
+
+                method = parsed;
             }
-            _ => {}
         }
     }
 
-    println!("method to use: {method_to_use}");
+    if verbose {
+        eprintln!("method to use: {method:?}");
+    }
 
-    let mut total_denominations: usize = 0;
-    let mut total_checks: usize = 0;
+    let total_denominations: usize;
+    let total_checks: usize;
     let mut max_size: usize = 0;
     let mut lines = std::io::stdin().lines();
     let mut denominations: Vec<usize> = Vec::new();
     let mut checks: Vec<usize> = Vec::new();
+    let mut stock: Vec<usize> = Vec::new();
+    let mut costs: Vec<usize> = Vec::new();
 
     {
         let Some(check) = lines.next() else {
@@ -131,7 +271,9 @@ fn main() {
         }
     }
 
-    println!("total denominations: {total_denominations}, total_checks: {total_checks}");
+    if verbose {
+        eprintln!("total denominations: {total_denominations}, total_checks: {total_checks}");
+    }
 
     {
         let Some(check) = lines.next() else {
@@ -149,7 +291,25 @@ fn main() {
         panic!("denominations provided nodes not match the specified amount. expected: {total_denominations} given: {}", denominations.len());
     }
 
-    while let Some(line) = lines.next() {
+    // every method works with the denominations sorted in ascending order.
+    // anything printed per denomination is put back in the order of the input
+    let order = denominations::Denominations::new(&denominations)
+        .unwrap_or_else(|err| panic!("{err}"));
+
+    denominations = order.sorted.clone();
+
+    // the bounded method expects the stock of each denomination and the
+    // weighted method expects the cost of each denomination on the line after
+    // the denominations
+    if method == Method::Bounded {
+        stock = order.to_sorted(&read_per_denomination(&mut lines, "stock", total_denominations));
+    }
+
+    if method == Method::Weighted {
+        costs = order.to_sorted(&read_per_denomination(&mut lines, "cost", total_denominations));
+    }
+
+    for line in lines {
         let line_check = line.expect("failed to read input from stdin");
 
         let Ok(value): Result<usize, _> = line_check.parse() else {
@@ -167,363 +327,477 @@ fn main() {
         panic!("checks provided does not match the specified amount. expected: {total_checks} given: {}\nvalues: {checks:?}", checks.len());
     }
 
+    if let Err(err) = denominations::validate_checks(&checks) {
+        panic!("{err}");
+    }
+
     if run_checks {
         print!("denominations:");
 
-        for dnmn in &denominations {
+        for dnmn in order.to_input(&denominations) {
             print!(" {}", dnmn);
         }
 
-        println!("");
+        println!();
     }
 
-    match method_to_use {
-        0 => {
-            let mut running_count = vec![0; denominations.len()];
-            let mut memorized_bottom_up = vec![(None, 0); max_size + 1];
+    match method {
+        Method::BottomUp | Method::BottomUpAlt | Method::TopDown => {
+            let found = calc_minimum_change(method, &denominations, &checks, max_size, verbose);
+            let input_denominations = order.to_input(&denominations);
 
-            calc_bottom_up(&denominations, &mut memorized_bottom_up);
+            for (value, maybe_change) in checks.iter().zip(found) {
+                let change = maybe_change.unwrap_or_else(|| Change::new(denominations.len()));
+                let amounts = order.to_input(&change.amounts);
 
-            for value in &checks {
-                let mut index = *value;
+                if run_checks {
+                    print!("{} => {}: ", value, change.total);
 
-                loop {
-                    let (Some(v), lu) = &memorized_bottom_up[index] else {
-                        break;
-                    };
+                    let mut count = 0;
 
-                    if *v == 0 {
-                        break;
-                    }
+                    for (amount, dnmn) in amounts.iter().zip(&input_denominations) {
+                        print!("{} ", amount);
 
-                    running_count[*lu] += 1;
-                    index = index - denominations[*lu];
-                }
+                        count += dnmn * amount;
+                    }
 
-                for count in &running_count {
-                    print!("{} ", count);
-                }
+                    print!("= {}", count);
 
-                println!("");
+                    if count != *value {
+                        println!(" invalid");
+                    } else {
+                        println!(" valid");
+                    }
+                } else {
+                    for count in &amounts {
+                        print!("{} ", count);
+                    }
 
-                running_count.fill(0);
+                    println!();
+                }
             }
         },
-        1 => {
-            let mut memorized_bottom_up = vec![State::Unset; max_size + 1];
+        Method::BottomUpUnique => {
+            let mut memorized_bottom_up_unique = vec![None; max_size + 1];
 
-            calc_bottom_up_alt(&denominations, &mut memorized_bottom_up);
+            calc_bottom_up_unique(&denominations, &mut memorized_bottom_up_unique, verbose);
 
             for value in &checks {
-                match &memorized_bottom_up[*value] {
-                    State::Set(change) => {
-                        if run_checks {
-                            print!("{} => {}: ", value, change.total);
+                print!("unique values for {value}");
 
-                            let mut count = 0;
+                if let Some(unique) = &memorized_bottom_up_unique[*value] {
+                    println!(" = {}", unique.amount);
 
-                            for dnmn_index in 0..denominations.len() {
-                                print!("{} ", change.amounts[dnmn_index]);
+                    for set in &unique.list {
+                        println!("{set:?}");
+                    }
+                } else {
+                    println!(" = 0");
+                }
+            }
 
-                                count += denominations[dnmn_index] * change.amounts[dnmn_index];
-                            }
+            for (index, maybe_unique) in memorized_bottom_up_unique.iter().enumerate() {
+                if let Some(unique) = maybe_unique {
+                    println!("unique values for {index} = {}", unique.amount);
 
-                            print!("= {}", count);
+                    for set in &unique.list {
+                        println!("{set:?}");
+                    }
+                } else {
+                    println!("{index} is unreachable");
+                }
+            }
+        },
+        Method::Count => {
+            if let Some(modulus) = modulus {
+                count_main(&denominations, &checks, max_size, ordered, count::Modular::zero(modulus), count::Modular::one(modulus));
+            } else {
+                count_main(&denominations, &checks, max_size, ordered, count::BigUint::zero(), count::BigUint::one());
+            }
+        },
+        Method::List => {
+            let limit = limit.unwrap_or(usize::MAX);
 
-                            if count != *value {
-                                println!(" invalid");
-                            } else {
-                                println!(" valid");
-                            }
-                        } else {
-                            for dnmn in &change.amounts {
-                                print!("{} ", dnmn);
-                            }
+            let name = if ordered {
+                "compositions"
+            } else {
+                "combinations"
+            };
+
+            for value in &checks {
+                println!("{name} for {value}");
 
-                            println!("");
+                if ordered {
+                    for sequence in combinations::Compositions::new(&denominations, *value).take(limit) {
+                        for index in sequence {
+                            print!("{} ", denominations[index]);
                         }
-                    },
-                    State::Invalid => {
-                        for _ in &denominations {
-                            print!("0 ");
+
+                        println!();
+                    }
+                } else {
+                    for counts in combinations::Combinations::new(&denominations, *value).take(limit) {
+                        for count in order.to_input(&counts) {
+                            print!("{} ", count);
                         }
 
-                        println!("");
-                    },
-                    State::Unset => unreachable!(),
+                        println!();
+                    }
                 }
             }
         },
-        2 => {
-            let mut memorized_top_down = vec![State::Unset; max_size + 1];
-
-            denominations.reverse();
+        Method::Bounded => {
+            let bounded_change = bounded::calc_bounded(&denominations, &stock, max_size);
 
             for value in &checks {
-                std::thread::scope(|scope| {
-                    let result = std::thread::Builder::new()
-                        .name(format!("top_down_{}", value))
-                        .stack_size(512 * 1024 * 1024)
-                        .spawn_scoped(scope, || {
-                            calc_top_down(*value, &denominations, &mut memorized_top_down, 1);
-                        });
-
-                    if let Err(err) = result {
-                        println!("thread error: {:#?}", err);
-                    }
-                });
+                let Some(change) = bounded_change.change_for(&denominations, *value) else {
+                    println!("impossible");
+                    continue;
+                };
 
-                match &memorized_top_down[*value] {
-                    State::Set(change) => {
-                        if run_checks {
-                            print!("{} => {}: ", value, change.total);
-                            let mut count = 0;
+                for count in order.to_input(&change.amounts) {
+                    print!("{} ", count);
+                }
 
-                            for dnmn_index in (denominations.len() - 1)..=0 {
-                                print!("{} ", change.amounts[dnmn_index]);
+                println!();
+            }
+        },
+        Method::Canonical => {
+            let found = match canonical::check(&denominations) {
+                canonical::Check::Canonical => {
+                    println!("canonical");
+                    return;
+                },
+                canonical::Check::NoneBelow(bound) => {
+                    println!("no counterexample below {bound}. amounts above it are not checked without a denomination of 1");
+                    return;
+                },
+                canonical::Check::Counterexample(found) => found,
+            };
 
-                                count += denominations[dnmn_index] * change.amounts[dnmn_index];
-                            }
+            println!("not canonical. smallest counterexample: {}", found.amount);
 
-                            println!("= {}", count);
-                        } else {
-                            for dnmn in change.amounts.iter().rev() {
-                                print!("{} ", dnmn);
-                            }
+            if let Some(greedy) = &found.greedy {
+                print!("greedy ({}):", greedy.total);
 
-                            println!("");
-                        }
+                for count in order.to_input(&greedy.amounts) {
+                    print!(" {}", count);
+                }
 
-                        for dnmn in change.amounts.iter().rev() {
-                            print!("{} ", dnmn);
-                        }
+                println!();
+            } else {
+                println!("greedy: can not make the amount");
+            }
 
-                        println!("");
-                    },
-                    State::Invalid => {
-                        for _ in &denominations {
-                            print!("0 ");
-                        }
+            print!("optimal ({}):", found.optimal.total);
 
-                        println!("");
-                    },
-                    State::Unset => unreachable!(),
-                }
+            for count in order.to_input(&found.optimal.amounts) {
+                print!(" {}", count);
             }
+
+            println!();
         },
-        3 => {
-            let mut memorized_bottom_up_unique = vec![None; max_size + 1];
+        Method::Frobenius => {
+            let divisor = frobenius::gcd_all(&denominations);
 
-            calc_bottom_up_unique(&denominations, &mut memorized_bottom_up_unique);
+            if divisor != 1 {
+                println!("denominations are not coprime (gcd: {divisor}). infinitely many amounts are unreachable");
+                return;
+            }
 
-            for value in &checks {
-                print!("unique values for {value}");
+            let residues = frobenius::calc_residues(&denominations);
 
-                if let Some(unique) = &memorized_bottom_up_unique[*value] {
-                    println!(" = {}", unique.amount);
+            let Some(number) = residues.frobenius_number() else {
+                println!("every amount is reachable");
+                return;
+            };
 
-                    for set in &unique.list {
-                        println!("{set:?}");
-                    }
-                } else {
-                    println!(" = 0");
-                }
+            println!("frobenius number: {number}");
+            print!("unreachable:");
+
+            for value in residues.unreachable_amounts() {
+                print!(" {}", value);
             }
 
-            for (index, maybe_unique) in memorized_bottom_up_unique.iter().enumerate() {
-                if let Some(unique) = maybe_unique {
-                    println!("unique values for {index} = {}", unique.amount);
+            println!();
+        },
+        Method::Weighted => {
+            let table = weighted::calc_weighted(&denominations, &costs, max_size);
 
-                    for set in &unique.list {
-                        println!("{set:?}");
-                    }
-                } else {
-                    println!("{index} is unreachable");
+            for value in &checks {
+                // amounts that can not be made print no coins
+                let change = table.change_for(&denominations, *value)
+                    .unwrap_or_else(|| Change::new(denominations.len()));
+
+                for count in order.to_input(&change.amounts) {
+                    print!("{} ", count);
                 }
+
+                println!();
+            }
+        },
+        Method::All => {
+            if !cross_check(&denominations, &checks, max_size, verbose) {
+                std::process::exit(1);
             }
         },
-        _ => unreachable!(),
     }
 }
 
-fn calc_top_down(change: usize, denominations: &[usize], memorized: &mut [State], context: usize) -> State {
-    if change == 0 {
-        #[cfg(debug_assertions)]
-        println!("{} {} base case", context, change);
-
-        return State::Set(Change::new(denominations.len()));
-    }
-
-    if let Some(found) = memorized[change].ref_change() {
-        #[cfg(debug_assertions)]
-        println!("{} {} memorized: {:?}", context, change, found);
+/// runs every minimum change method and compares the totals that they find
+/// for each check. every change found is also checked to add up to the
+/// check. returns false if anything did not match
+fn cross_check(denominations: &[usize], checks: &[usize], max_size: usize, verbose: bool) -> bool {
+    let results: Vec<Vec<Option<Change>>> = MINIMUM_CHANGE_METHODS.iter()
+        .map(|method| calc_minimum_change(*method, denominations, checks, max_size, verbose))
+        .collect();
+
+    let mut mismatches = 0;
+
+    for (index, value) in checks.iter().enumerate() {
+        let mut totals = Vec::with_capacity(MINIMUM_CHANGE_METHODS.len());
+        let mut valid = true;
+
+        for found in &results {
+            let total = found[index].as_ref().map(|change| {
+                let sum: usize = change.amounts.iter()
+                    .zip(denominations)
+                    .map(|(amount, dnmn)| amount * dnmn)
+                    .sum();
+
+                if sum != *value || change.amounts.iter().sum::<usize>() != change.total {
+                    valid = false;
+                }
 
-        return State::Set(found.clone());
-    }
+                change.total
+            });
 
-    let mut lowest = State::Invalid;
+            totals.push(total);
+        }
 
-    for dnmn in 0..denominations.len() {
-        if denominations[dnmn] > change {
-            #[cfg(debug_assertions)]
-            println!("{} {} < dnmn {}", context, change, denominations[dnmn]);
+        if valid && totals.windows(2).all(|pair| pair[0] == pair[1]) {
+            match totals[0] {
+                Some(total) => println!("{value} = {total}"),
+                None => println!("{value} is unreachable"),
+            }
 
             continue;
         }
 
-        #[cfg(debug_assertions)]
-        println!("{} {} sub: {}", context, change, denominations[dnmn]);
+        mismatches += 1;
 
-        let mut result = match calc_top_down(change - denominations[dnmn], denominations, memorized, context + 1) {
-            State::Set(change) => change,
-            State::Unset => unreachable!(),
-            State::Invalid => continue,
-        };
-
-        lowest = match lowest {
-            State::Set(curr) => {
-                if curr.total > result.total {
-                    result.total += 1;
-                    result.amounts[dnmn] += 1;
+        print!("{value} mismatch:");
 
-                    #[cfg(debug_assertions)]
-                    println!("{} {} updating lowest: {:?} prev: {:?}", context, change, result, curr);
+        for (method, total) in MINIMUM_CHANGE_METHODS.iter().zip(&totals) {
+            match total {
+                Some(total) => print!(" {method:?} = {total}"),
+                None => print!(" {method:?} = unreachable"),
+            }
+        }
 
-                    State::Set(result)
-                } else {
-                    State::Set(curr)
-                }
-            },
-            State::Unset => unreachable!(),
-            State::Invalid => {
-                result.total += 1;
-                result.amounts[dnmn] += 1;
+        if !valid {
+            print!(" (invalid change)");
+        }
 
-                #[cfg(debug_assertions)]
-                println!("{} {} setting lowest: {:?}", context, change, result);
+        println!();
+    }
 
-                State::Set(result)
-            }
-        };
+    if mismatches > 0 {
+        println!("{mismatches} check(s) did not match");
     }
 
-    memorized[change] = lowest;
+    mismatches == 0
+}
 
-    #[cfg(debug_assertions)]
-    println!("{} {} result: {:?}", context, change, memorized[change]);
+/// prints the number of ways to make change for each check
+fn count_main<C: count::Count>(dnmn: &[usize], checks: &[usize], max_size: usize, ordered: bool, zero: C, one: C) {
+    let (name, ways) = if ordered {
+        ("compositions", count::count_compositions(dnmn, max_size, zero, one))
+    } else {
+        ("combinations", count::count_combinations(dnmn, max_size, zero, one))
+    };
 
-    memorized[change].clone()
+    for value in checks {
+        println!("{name} for {value} = {}", ways[*value]);
+    }
 }
 
-fn calc_bottom_up_alt(denominations: &[usize], memorized: &mut [State]) {
-    let dnmn_len = denominations.len();
-
-    memorized[0] = State::Set(Change::new(dnmn_len));
+/// a value waiting on the stack of the top down method along with the index
+/// of the next denomination to check
+///
+/// both are stored as u32 the same as the change table so a stack as deep
+/// as the largest table still fits in memory
+struct Frame {
+    value: u32,
+    next: u32,
+}
 
-    for index in 1..memorized.len() {
-        let mut min = State::Invalid;
+/// calculates the minimum change for the given value from the top down
+///
+/// the recursion is replaced with an explicit stack so large values do not
+/// overflow the thread stack. each frame goes through the denominations in
+/// order and stops at the first dependency that is not memorized yet,
+/// pushing it on to the stack and resuming from the next denomination once
+/// it is done. this keeps at most one frame per level of the recursion.
+/// when more than one denomination gives the fewest coins the first one is
+/// used.
+fn calc_top_down(change: usize, denominations: &[usize], table: &mut ChangeTable, verbose: bool) {
+    if table.get(change) != Slot::Unset {
+        return;
+    }
 
-        #[cfg(debug_assertions)]
-        println!("{}", index);
+    // the table only holds amounts that fit in a u32
+    let mut stack = vec![Frame {
+        value: change as u32,
+        next: 0,
+    }];
 
-        for dnmn in 0..dnmn_len {
-            #[cfg(debug_assertions)]
-            print!("├─── {}", denominations[dnmn]);
+    while let Some(frame) = stack.last_mut() {
+        let current = frame.value as usize;
+        let mut pending = None;
 
-            if denominations[dnmn] > index {
-                #[cfg(debug_assertions)]
-                println!(" skip");
+        while let Some(dnmn) = denominations.get(frame.next as usize) {
+            frame.next += 1;
 
+            if *dnmn <= current && table.get(current - dnmn) == Slot::Unset {
+                pending = Some(current - dnmn);
                 break;
             }
+        }
+
+        if let Some(value) = pending {
+            if verbose {
+                eprintln!("{} {} pending: {}", stack.len(), current, value);
+            }
 
-            let Some(cmp) = memorized[index - denominations[dnmn]].ref_change() else {
-                #[cfg(debug_assertions)]
-                println!(" no change for previous");
+            stack.push(Frame {
+                value: value as u32,
+                next: 0,
+            });
 
+            continue;
+        }
+
+        let mut lowest: Option<(usize, usize)> = None;
+
+        for (index, dnmn) in denominations.iter().enumerate() {
+            if *dnmn > current {
+                continue;
+            }
+
+            let Some(coins) = table.coins(current - dnmn) else {
                 continue;
             };
 
-            #[cfg(debug_assertions)]
-            print!(" {:?}", cmp);
+            if lowest.is_none_or(|(lowest_coins, _)| coins + 1 < lowest_coins) {
+                lowest = Some((coins + 1, index));
+            }
+        }
+
+        if verbose {
+            eprintln!("{} {} result: {:?}", stack.len(), current, lowest);
+        }
 
-            min = match min {
-                State::Set(curr) => {
-                    let mut rtn = cmp.clone();
-                    rtn.total += 1;
-                    rtn.amounts[dnmn] += 1;
+        if let Some((coins, last_used)) = lowest {
+            table.set(current, coins, last_used);
+        } else {
+            table.set_invalid(current);
+        }
 
-                    #[cfg(debug_assertions)]
-                    print!(" {} < {}", curr.total, rtn.total);
+        stack.pop();
+    }
+}
 
-                    if curr.total <= rtn.total {
-                        #[cfg(debug_assertions)]
-                        println!(" no update");
+/// calculates the minimum change for every amount in the table from the
+/// bottom up
+///
+/// the denominations are expected to be in ascending order so that the
+/// search for an amount stops at the first denomination that is too large
+fn calc_bottom_up_alt(denominations: &[usize], table: &mut ChangeTable, verbose: bool) {
+    for index in 1..table.len() {
+        let mut min: Option<usize> = None;
+        let mut last_used = 0;
+
+        if verbose {
+            eprintln!("{}", index);
+        }
 
-                        State::Set(curr)
-                    } else {
-                        #[cfg(debug_assertions)]
-                        println!(" updating to {:?}", rtn);
+        for (dnmn, value) in denominations.iter().enumerate() {
+            if verbose {
+                eprint!("├─── {}", value);
+            }
 
-                        State::Set(rtn)
-                    }
-                },
-                State::Unset => unreachable!(),
-                State::Invalid => {
-                    let mut rtn = cmp.clone();
-                    rtn.total += 1;
-                    rtn.amounts[dnmn] += 1;
+            if *value > index {
+                if verbose {
+                    eprintln!(" skip");
+                }
 
-                    #[cfg(debug_assertions)]
-                    println!(" setting to {:?}", rtn);
+                break;
+            }
 
-                    State::Set(rtn)
+            let Some(cmp) = table.coins(index - value) else {
+                if verbose {
+                    eprintln!(" no change for previous");
                 }
+
+                continue;
             };
+
+            if min.is_some_and(|curr| curr <= cmp + 1) {
+                if verbose {
+                    eprintln!(" no update");
+                }
+
+                continue;
+            }
+
+            if verbose {
+                eprintln!(" updating to {} coins", cmp + 1);
+            }
+
+            min = Some(cmp + 1);
+            last_used = dnmn;
         }
 
-        #[cfg(debug_assertions)]
-        println!("└─── memorizing {:?}", min);
+        if verbose {
+            eprintln!("└─── memorizing {:?}", min);
+        }
 
-        memorized[index] = min;
+        if let Some(min) = min {
+            table.set(index, min, last_used);
+        } else {
+            table.set_invalid(index);
+        }
     }
 }
 
-fn calc_bottom_up(dnmn: &[usize], mem: &mut [(Option<usize>, usize)]) {
-    mem[0] = (Some(0), 0);
-
-    let mut min = None;
-    let mut last_used = 0;
+fn calc_bottom_up(dnmn: &[usize], table: &mut ChangeTable) {
+    for i in 1..table.len() {
+        let mut min: Option<usize> = None;
+        let mut last_used = 0;
 
-    for i in 1..mem.len() {
-        min = None;
-        last_used = 0;
-
-        for d in 0..dnmn.len() {
-            if dnmn[d] > i {
+        for (d, value) in dnmn.iter().enumerate() {
+            if *value > i {
                 continue;
             }
 
-            let (Some(check), _) = &mem[i - dnmn[d]] else {
+            let Some(check) = table.coins(i - value) else {
                 continue;
             };
 
             let cmp = check + 1;
 
-            min = Some(if let Some(min) = min {
-                if min <= cmp {
-                    min
-                } else {
-                    last_used = d;
-                    cmp
-                }
-            } else {
+            if min.is_none_or(|min| cmp < min) {
+                min = Some(cmp);
                 last_used = d;
-                cmp
-            });
+            }
         }
 
-        mem[i] = (min, last_used);
+        if let Some(min) = min {
+            table.set(i, min, last_used);
+        } else {
+            table.set_invalid(i);
+        }
     }
 }
 
@@ -533,7 +807,7 @@ struct Unique {
     amount: usize,
 }
 
-fn calc_bottom_up_unique(dnmn: &[usize], mem: &mut [Option<Unique>]) {
+fn calc_bottom_up_unique(dnmn: &[usize], mem: &mut [Option<Unique>], verbose: bool) {
     mem[0] = Some(Unique {
         list: Vec::new(),
         amount: 0,
@@ -545,25 +819,37 @@ fn calc_bottom_up_unique(dnmn: &[usize], mem: &mut [Option<Unique>]) {
         let mut amount = 0;
 
         for d in 0..dnmn.len() {
-            print!("checking {i} dnmn: {}", dnmn[d]);
+            if verbose {
+                eprint!("checking {i} dnmn: {}", dnmn[d]);
+            }
 
             if dnmn[d] > i {
-                println!(" greater than value");
+                if verbose {
+                    eprintln!(" greater than value");
+                }
+
                 continue;
             }
 
             let Some(prev) = &mem[i - dnmn[d]] else {
-                println!(" previous value not possible");
+                if verbose {
+                    eprintln!(" previous value not possible");
+                }
+
                 continue;
             };
 
             if prev.list.is_empty() {
-                println!(" pushing unique set");
+                if verbose {
+                    eprintln!(" pushing unique set");
+                }
 
                 sets.push(vec![dnmn[d]]);
                 amount += 1;
             } else {
-                println!(" cloning previous values at {}. total: {}", i - dnmn[d], prev.list.len());
+                if verbose {
+                    eprintln!(" cloning previous values at {}. total: {}", i - dnmn[d], prev.list.len());
+                }
 
                 for set in &prev.list {
                     let mut cp = set.clone();
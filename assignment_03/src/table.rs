@@ -0,0 +1,116 @@
+use crate::Change;
+
+/// marks an amount that has not been calculated yet
+const UNSET: u32 = u32::MAX;
+/// marks an amount that can not be made
+const INVALID: u32 = u32::MAX - 1;
+
+/// the fewest coins for an amount along with the index of the last
+/// denomination used
+///
+/// both values are stored as u32 so an entry is 8 bytes which lets a table
+/// for amounts up to 10^8 fit in under a gigabyte
+#[derive(Debug, Clone, Copy)]
+struct Entry {
+    coins: u32,
+    last_used: u32,
+}
+
+/// the state of a single amount in the table
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Slot {
+    Set {
+        coins: usize,
+        last_used: usize,
+    },
+    Unset,
+    Invalid,
+}
+
+/// a back pointer table of the minimum change for every amount up to a max
+/// size
+///
+/// instead of storing the amount of each denomination used for every entry
+/// only the last denomination used is kept. the full change for an amount
+/// is rebuilt on demand by following the last denomination used back to 0.
+pub struct ChangeTable {
+    entries: Vec<Entry>,
+}
+
+impl ChangeTable {
+    /// creates a table where every amount except 0 is unset
+    pub fn new(max_size: usize) -> Self {
+        if max_size >= INVALID as usize {
+            panic!("max size is too large for the change table: {max_size}");
+        }
+
+        let mut entries = vec![Entry { coins: UNSET, last_used: 0 }; max_size + 1];
+        entries[0].coins = 0;
+
+        ChangeTable {
+            entries,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn get(&self, amount: usize) -> Slot {
+        let entry = self.entries[amount];
+
+        match entry.coins {
+            UNSET => Slot::Unset,
+            INVALID => Slot::Invalid,
+            coins => Slot::Set {
+                coins: coins as usize,
+                last_used: entry.last_used as usize,
+            },
+        }
+    }
+
+    /// the fewest coins needed for the amount, None if it is not set or can
+    /// not be made
+    pub fn coins(&self, amount: usize) -> Option<usize> {
+        match self.get(amount) {
+            Slot::Set { coins, .. } => Some(coins),
+            _ => None,
+        }
+    }
+
+    pub fn set(&mut self, amount: usize, coins: usize, last_used: usize) {
+        self.entries[amount] = Entry {
+            coins: coins as u32,
+            last_used: last_used as u32,
+        };
+    }
+
+    pub fn set_invalid(&mut self, amount: usize) {
+        self.entries[amount].coins = INVALID;
+    }
+
+    /// rebuilds the change for the amount by following the last
+    /// denomination used back to 0. returns None if the amount can not be
+    /// made
+    pub fn change_for(&self, dnmn: &[usize], value: usize) -> Option<Change> {
+        let Slot::Set { coins, .. } = self.get(value) else {
+            return None;
+        };
+
+        let mut rtn = Change::new(dnmn.len());
+        let mut index = value;
+
+        rtn.total = coins;
+
+        while index > 0 {
+            let Slot::Set { last_used, .. } = self.get(index) else {
+                unreachable!();
+            };
+
+            rtn.amounts[last_used] += 1;
+            index -= dnmn[last_used];
+        }
+
+        Some(rtn)
+    }
+}
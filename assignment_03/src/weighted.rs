@@ -0,0 +1,84 @@
+use crate::Change;
+use crate::table::{ChangeTable, Slot};
+
+/// the cheapest change for every amount up to a max size
+///
+/// the amount of coins and the last denomination used are kept in a change
+/// table so the change is rebuilt the same way as the other methods
+pub struct WeightedTable {
+    table: ChangeTable,
+}
+
+impl WeightedTable {
+    /// rebuilds the change for the amount. returns None if the amount can not
+    /// be made
+    pub fn change_for(&self, dnmn: &[usize], value: usize) -> Option<Change> {
+        // amounts whose cheapest change costs more than a usize are never set
+        if self.table.get(value) == Slot::Unset {
+            panic!("total cost overflowed for amount {value}");
+        }
+
+        self.table.change_for(dnmn, value)
+    }
+}
+
+/// calculates the cheapest change for every amount up to max_size where
+/// each coin of denomination i costs costs[i]
+///
+/// works the same as `calc_bottom_up` except that the total cost is
+/// minimized first and the amount of coins is only used to break ties. a
+/// coin that would overflow the total cost is never cheaper than one that
+/// does not. the total cost of each amount is only kept while the table is
+/// built.
+pub fn calc_weighted(dnmn: &[usize], costs: &[usize], max_size: usize) -> WeightedTable {
+    let mut table = ChangeTable::new(max_size);
+    let mut totals: Vec<usize> = vec![0; max_size + 1];
+
+    for i in 1..table.len() {
+        let mut min: Option<(usize, usize)> = None;
+        let mut last_used = 0;
+        let mut overflowed = false;
+
+        for (d, (value, cost)) in dnmn.iter().zip(costs).enumerate() {
+            if *value == 0 || *value > i {
+                continue;
+            }
+
+            let prev_coins = match table.get(i - value) {
+                Slot::Set { coins, .. } => coins,
+                Slot::Unset => {
+                    overflowed = true;
+                    continue;
+                },
+                Slot::Invalid => continue,
+            };
+
+            let Some(check_cost) = totals[i - value].checked_add(*cost) else {
+                overflowed = true;
+                continue;
+            };
+
+            let cmp = (check_cost, prev_coins + 1);
+
+            if min.is_none_or(|known| cmp < known) {
+                min = Some(cmp);
+                last_used = d;
+            }
+        }
+
+        match min {
+            Some((cost, coins)) => {
+                totals[i] = cost;
+                table.set(i, coins, last_used);
+            },
+            // left unset so that the overflow is reported if the amount is
+            // checked and passed on to every amount built from it
+            None if overflowed => {},
+            None => table.set_invalid(i),
+        }
+    }
+
+    WeightedTable {
+        table,
+    }
+}
@@ -0,0 +1,214 @@
+use crate::{Cost, EditKind};
+use crate::matrix::Substitution;
+
+/// a value larger than any real cost that still leaves room for costs to be
+/// added to it without overflowing
+const UNREACHABLE: Cost = Cost::MAX / 4;
+
+/// the costs of an alignment where every run of gaps costs `open` plus the
+/// cost of each token in it
+pub struct GapCosts {
+    pub ins: Cost,
+    pub del: Cost,
+    pub sub: Cost,
+    pub open: Cost,
+}
+
+/// every pair of tokens costs `sub`
+impl<T> Substitution<T> for GapCosts {
+    fn value(&self, _: &T, _: &T) -> Cost {
+        self.sub
+    }
+}
+
+/// the edit that an alignment ends with in each layer of the grid
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Layer {
+    /// a match or substitution
+    Diagonal,
+    /// a token of from is deleted
+    Deletion,
+    /// a token of to is inserted
+    Insertion,
+}
+
+/// the order that layers are preferred in when more than one is optimal
+const LAYERS: [Layer; 3] = [Layer::Diagonal, Layer::Deletion, Layer::Insertion];
+
+/// the three layers of the grid, each stored as a single list with one row
+/// for every index of to
+struct Layers<'a, T> {
+    from: &'a [T],
+    to: &'a [T],
+    costs: &'a GapCosts,
+    subs: &'a dyn Substitution<T>,
+    diagonal: Vec<Cost>,
+    deletion: Vec<Cost>,
+    insertion: Vec<Cost>,
+}
+
+impl<T: Eq> Layers<'_, T> {
+    fn cell(&self, from_index: usize, to_index: usize) -> usize {
+        to_index * (self.from.len() + 1) + from_index
+    }
+
+    fn get(&self, layer: Layer, from_index: usize, to_index: usize) -> Cost {
+        let cell = self.cell(from_index, to_index);
+
+        match layer {
+            Layer::Diagonal => self.diagonal[cell],
+            Layer::Deletion => self.deletion[cell],
+            Layer::Insertion => self.insertion[cell],
+        }
+    }
+
+    fn diagonal_cost(&self, from_index: usize, to_index: usize) -> Cost {
+        let a = &self.from[from_index - 1];
+        let b = &self.to[to_index - 1];
+
+        let sub = self.subs.value(a, b);
+
+        if a == b {
+            sub.min(0)
+        } else {
+            sub
+        }
+    }
+
+    /// the cost of entering `layer` at the cell from each layer of the cell
+    /// before it, None if there is no cell before it
+    fn candidates(&self, layer: Layer, from_index: usize, to_index: usize) -> Option<[Cost; 3]> {
+        let (prev_from, prev_to, cost) = match layer {
+            Layer::Diagonal => {
+                if from_index == 0 || to_index == 0 {
+                    return None;
+                }
+
+                (from_index - 1, to_index - 1, self.diagonal_cost(from_index, to_index))
+            },
+            Layer::Deletion => {
+                if from_index == 0 {
+                    return None;
+                }
+
+                (from_index - 1, to_index, self.costs.del)
+            },
+            Layer::Insertion => {
+                if to_index == 0 {
+                    return None;
+                }
+
+                (from_index, to_index - 1, self.costs.ins)
+            },
+        };
+
+        Some(LAYERS.map(|prev| {
+            // staying in the same gap layer extends the gap instead of
+            // opening a new one
+            let open = if prev == layer || layer == Layer::Diagonal {
+                0
+            } else {
+                self.costs.open
+            };
+
+            self.get(prev, prev_from, prev_to) + open + cost
+        }))
+    }
+
+    /// the best cost of the cell in the layer
+    fn best(&self, layer: Layer, from_index: usize, to_index: usize) -> Cost {
+        match self.candidates(layer, from_index, to_index) {
+            Some(list) => list.into_iter().min().unwrap().min(UNREACHABLE),
+            None => UNREACHABLE,
+        }
+    }
+}
+
+/// picks the first layer in order of preference that has the given cost
+fn pick(list: [Cost; 3], cost: Cost) -> Layer {
+    LAYERS.into_iter()
+        .zip(list)
+        .find(|(_, check)| *check == cost)
+        .map(|(layer, _)| layer)
+        .unwrap()
+}
+
+/// finds the minimum edit distance between the two slices where each run of
+/// insertions or deletions also costs the gap open cost once
+///
+/// this is gotoh's method. the grid is split in to three layers that hold
+/// the best cost of an alignment ending with a match or substitution, a
+/// deletion or an insertion. a gap only pays the open cost when it is
+/// entered from a different layer. when more than one edit is optimal a
+/// match or substitution is preferred, then a deletion and then an
+/// insertion.
+pub fn edit_distance<T: Eq>(from: &[T], to: &[T], costs: &GapCosts, subs: &dyn Substitution<T>) -> (Cost, Vec<EditKind>) {
+    let total = (from.len() + 1) * (to.len() + 1);
+
+    let mut layers = Layers {
+        from,
+        to,
+        costs,
+        subs,
+        diagonal: vec![UNREACHABLE; total],
+        deletion: vec![UNREACHABLE; total],
+        insertion: vec![UNREACHABLE; total],
+    };
+
+    layers.diagonal[0] = 0;
+
+    for to_index in 0..=to.len() {
+        for from_index in 0..=from.len() {
+            if from_index == 0 && to_index == 0 {
+                continue;
+            }
+
+            let cell = layers.cell(from_index, to_index);
+
+            layers.diagonal[cell] = layers.best(Layer::Diagonal, from_index, to_index);
+            layers.deletion[cell] = layers.best(Layer::Deletion, from_index, to_index);
+            layers.insertion[cell] = layers.best(Layer::Insertion, from_index, to_index);
+        }
+    }
+
+    let mut from_index = from.len();
+    let mut to_index = to.len();
+
+    let ends = LAYERS.map(|layer| layers.get(layer, from_index, to_index));
+    let cost = ends.into_iter().min().unwrap();
+    let mut layer = pick(ends, cost);
+
+    let mut edits = Vec::with_capacity(from.len() + to.len());
+
+    while from_index != 0 || to_index != 0 {
+        let current = layers.get(layer, from_index, to_index);
+        let list = layers.candidates(layer, from_index, to_index).unwrap();
+
+        match layer {
+            Layer::Diagonal => {
+                if from[from_index - 1] == to[to_index - 1] {
+                    edits.push(EditKind::Mat);
+                } else {
+                    edits.push(EditKind::Sub);
+                }
+
+                from_index -= 1;
+                to_index -= 1;
+            },
+            Layer::Deletion => {
+                edits.push(EditKind::Del);
+                from_index -= 1;
+            },
+            Layer::Insertion => {
+                edits.push(EditKind::Ins);
+                to_index -= 1;
+            },
+        }
+
+        layer = pick(list, current);
+    }
+
+    edits.reverse();
+
+    (cost, edits)
+}
@@ -0,0 +1,181 @@
+use std::ops::Range;
+
+use crate::{Cost, Edit, EditKind};
+use crate::matrix::Substitution;
+
+/// which parts of the inputs have to be covered by an alignment
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Scope {
+    /// the best scoring pair of substrings of the inputs
+    Local,
+    /// both inputs are covered but gaps at the start or end of either one
+    /// are free
+    SemiGlobal,
+}
+
+/// the scores used when maximizing an alignment. insertions, deletions and
+/// substitutions are penalties that are taken away from the score and
+/// matches are added to it
+pub struct Scores {
+    pub ins: Cost,
+    pub del: Cost,
+    pub sub: Cost,
+    pub mat: Cost,
+}
+
+/// matching tokens add `mat` to the score and any other pair takes `sub`
+/// away from it
+impl<T: Eq> Substitution<T> for Scores {
+    fn value(&self, a: &T, b: &T) -> Cost {
+        if a == b {
+            self.mat
+        } else {
+            -self.sub
+        }
+    }
+}
+
+/// the best alignment found along with the tokens of each input it covers
+pub struct Alignment {
+    pub score: Cost,
+    pub edits: Vec<EditKind>,
+    pub from: Range<usize>,
+    pub to: Range<usize>,
+}
+
+/// fills the grid with the best score of an alignment ending at each cell
+///
+/// the first row and column are 0 for both scopes since the alignment can
+/// start anywhere along them. a local alignment also starts over whenever
+/// the score would go below 0.
+fn fill<T: Eq>(
+    from: &[T],
+    to: &[T],
+    scores: &Scores,
+    subs: &dyn Substitution<T>,
+    scope: Scope
+) -> Vec<Vec<Edit>> {
+    let mut grid = vec![vec![Edit::mat(0); from.len() + 1]; to.len() + 1];
+
+    for row in grid.iter_mut().skip(1) {
+        row[0] = Edit::ins(0);
+    }
+
+    for cell in grid[0].iter_mut().skip(1) {
+        *cell = Edit::del(0);
+    }
+
+    for from_index in 1..=from.len() {
+        for to_index in 1..=to.len() {
+            let diagonal = grid[to_index - 1][from_index - 1].value;
+            let curr_del = grid[to_index][from_index - 1].value - scores.del;
+            let curr_ins = grid[to_index - 1][from_index].value - scores.ins;
+
+            let a = &from[from_index - 1];
+            let b = &to[to_index - 1];
+
+            let mut max = if a == b {
+                Edit::mat(diagonal + subs.value(a, b))
+            } else {
+                Edit::sub(diagonal + subs.value(a, b))
+            };
+
+            if curr_del > max.value {
+                max = Edit::del(curr_del);
+            }
+
+            if curr_ins > max.value {
+                max = Edit::ins(curr_ins);
+            }
+
+            if scope == Scope::Local && max.value < 0 {
+                max = Edit::mat(0);
+            }
+
+            grid[to_index][from_index] = max;
+        }
+    }
+
+    grid
+}
+
+/// finds the cell with the highest score that an alignment is allowed to
+/// end at. the first one found is kept when more than one has the same
+/// score
+fn find_end(grid: &[Vec<Edit>], scope: Scope) -> (usize, usize) {
+    let to_len = grid.len() - 1;
+    let from_len = grid[0].len() - 1;
+
+    let cells: Vec<(usize, usize)> = match scope {
+        Scope::Local => (0..=from_len)
+            .flat_map(|from_index| (0..=to_len).map(move |to_index| (from_index, to_index)))
+            .collect(),
+        // the full alignment is preferred when it ties with one that leaves
+        // a gap at the end
+        Scope::SemiGlobal => std::iter::once((from_len, to_len))
+            .chain((0..from_len).map(|from_index| (from_index, to_len)))
+            .chain((0..to_len).map(|to_index| (from_len, to_index)))
+            .collect(),
+    };
+
+    let mut rtn = cells[0];
+
+    for (from_index, to_index) in cells {
+        if grid[to_index][from_index].value > grid[rtn.1][rtn.0].value {
+            rtn = (from_index, to_index);
+        }
+    }
+
+    rtn
+}
+
+/// finds the best scoring local or semi-global alignment of the two slices
+///
+/// the grid is followed back from the best cell with the same traceback as
+/// the global edit distance. a local alignment stops once the score drops
+/// to 0 and a semi-global alignment stops at the first row or column since
+/// any gaps before that are free.
+///
+/// `subs` gives the score of each pair of tokens which is added to the
+/// score, so a substitution matrix holds scores instead of costs here
+pub fn align<T>(
+    from: &[T],
+    to: &[T],
+    scores: &Scores,
+    subs: &dyn Substitution<T>,
+    scope: Scope,
+    verbose: bool
+) -> Alignment
+where
+    T: Eq + std::fmt::Display
+{
+    let grid = fill(from, to, scores, subs, scope);
+
+    if verbose {
+        let longest = grid.iter()
+            .flatten()
+            .map(|edit| edit.value.to_string().len())
+            .max()
+            .unwrap_or(1);
+
+        crate::print_grid(from, to, &grid, longest);
+    }
+
+    let (end_from, end_to) = find_end(&grid, scope);
+
+    let found = match scope {
+        Scope::Local => crate::traceback(&grid, end_from, end_to, |edit, _, _| {
+            edit.value == 0
+        }, verbose),
+        Scope::SemiGlobal => crate::traceback(&grid, end_from, end_to, |_, from_index, to_index| {
+            from_index == 0 || to_index == 0
+        }, verbose),
+    };
+
+    Alignment {
+        score: grid[end_to][end_from].value,
+        edits: found.edits,
+        from: found.from_index..end_from,
+        to: found.to_index..end_to,
+    }
+}
@@ -0,0 +1,65 @@
+use crate::subsequence::{self, Direction, Subsequence};
+
+/// the shape of a bitonic subsequence
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Shape {
+    /// increasing up to a peak and then decreasing
+    Peak,
+    /// decreasing down to a valley and then increasing
+    Valley,
+}
+
+impl std::fmt::Display for Shape {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Shape::Peak => write!(f, "increasing then decreasing"),
+            Shape::Valley => write!(f, "decreasing then increasing"),
+        }
+    }
+}
+
+/// finds one of the longest bitonic subsequences of the list in O(n log n)
+///
+/// the list is patience sorted once forwards for the first half and once
+/// backwards for the second half. the longest subsequence that turns at
+/// index i is the one ending at i in the forward pass joined with the one
+/// ending at i in the backward pass, counting i only once. either half is
+/// allowed to be empty so a plain monotonic subsequence also counts.
+pub fn longest<T: Ord + Clone>(list: &[T], shape: Shape, strict: bool) -> Subsequence<T> {
+    let first_direction = match shape {
+        Shape::Peak => Direction::Increasing,
+        Shape::Valley => Direction::Decreasing,
+    };
+
+    let reversed: Vec<T> = list.iter().rev().cloned().collect();
+    let forward = subsequence::patience_sort(list, first_direction, strict);
+    let backward = subsequence::patience_sort(&reversed, first_direction, strict);
+
+    let turn = (0..list.len()).max_by_key(|index| {
+        // ties are broken by the earliest index
+        (forward.lengths[*index] + backward.lengths[list.len() - 1 - index], std::cmp::Reverse(*index))
+    });
+
+    let Some(turn) = turn else {
+        return Subsequence {
+            indices: Vec::new(),
+            values: Vec::new(),
+        };
+    };
+
+    let mut indices = forward.chain_to(turn);
+
+    // the backward chain ends at the turn as well so it is skipped here
+    let second_half = backward.chain_to(list.len() - 1 - turn);
+
+    indices.extend(second_half.iter().rev().skip(1).map(|index| list.len() - 1 - index));
+
+    let values = indices.iter()
+        .map(|index| list[*index].clone())
+        .collect();
+
+    Subsequence {
+        indices,
+        values,
+    }
+}
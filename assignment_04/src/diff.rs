@@ -0,0 +1,153 @@
+use std::ops::Range;
+
+use crate::EditKind;
+
+/// a line of a hunk along with its index in the input it comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Line {
+    /// an unchanged line, indexed in from
+    Context(usize),
+    /// a line of from that is removed
+    Removed(usize),
+    /// a line of to that is added
+    Added(usize),
+}
+
+/// a group of changes that are close together along with the unchanged
+/// lines around them
+pub struct Hunk {
+    pub from: Range<usize>,
+    pub to: Range<usize>,
+    pub lines: Vec<Line>,
+}
+
+/// formats a range of lines the same way as the unified format of gnu diff.
+/// lines start from 1 and an empty range starts at the line before it
+fn unified_range(range: &Range<usize>) -> String {
+    match range.len() {
+        0 => format!("{},0", range.start),
+        1 => format!("{}", range.start + 1),
+        len => format!("{},{len}", range.start + 1),
+    }
+}
+
+impl std::fmt::Display for Hunk {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "@@ -{} +{} @@", unified_range(&self.from), unified_range(&self.to))
+    }
+}
+
+/// a run of edits that are not matches
+struct Change {
+    from: Range<usize>,
+    to: Range<usize>,
+}
+
+/// splits the edits in to runs of changed lines, also returning the length
+/// of from
+fn changes(edits: &[EditKind]) -> (Vec<Change>, usize) {
+    let mut rtn = Vec::new();
+    let mut current: Option<Change> = None;
+    let mut from_index = 0;
+    let mut to_index = 0;
+
+    for kind in edits {
+        if let EditKind::Mat = kind {
+            rtn.extend(current.take());
+            from_index += 1;
+            to_index += 1;
+
+            continue;
+        }
+
+        let change = current.get_or_insert(Change {
+            from: from_index..from_index,
+            to: to_index..to_index,
+        });
+
+        match kind {
+            EditKind::Sub => {
+                from_index += 1;
+                to_index += 1;
+            },
+            EditKind::Del => {
+                from_index += 1;
+            },
+            EditKind::Ins => {
+                to_index += 1;
+            },
+            EditKind::Tra => {
+                from_index += 2;
+                to_index += 2;
+            },
+            EditKind::Mat => unreachable!(),
+        }
+
+        change.from.end = from_index;
+        change.to.end = to_index;
+    }
+
+    rtn.extend(current);
+
+    (rtn, from_index)
+}
+
+/// groups the edits that turn from in to to in to hunks with up to
+/// `context` unchanged lines before and after each change
+///
+/// changes that are separated by no more than twice the context share a
+/// hunk. within a run of changes every removed line is listed before the
+/// added lines.
+pub fn hunks(edits: &[EditKind], context: usize) -> Vec<Hunk> {
+    let (list, from_len) = changes(edits);
+    let mut groups: Vec<Vec<Change>> = Vec::new();
+
+    for change in list {
+        if let Some(group) = groups.last_mut() {
+            let last = group.last().unwrap();
+
+            if change.from.start - last.from.end <= context * 2 {
+                group.push(change);
+                continue;
+            }
+        }
+
+        groups.push(vec![change]);
+    }
+
+    let mut rtn = Vec::with_capacity(groups.len());
+
+    for group in groups {
+        let first = group.first().unwrap();
+        let last = group.last().unwrap();
+
+        // the lines around a group are all unchanged so the amount of them
+        // is the same in both inputs
+        let before = context.min(first.from.start);
+        let after = context.min(from_len - last.from.end);
+
+        let from = (first.from.start - before)..(last.from.end + after);
+        let to = (first.to.start - before)..(last.to.end + after);
+
+        let mut lines = Vec::new();
+        let mut index = from.start;
+
+        for change in &group {
+            lines.extend((index..change.from.start).map(Line::Context));
+            lines.extend(change.from.clone().map(Line::Removed));
+            lines.extend(change.to.clone().map(Line::Added));
+
+            index = change.from.end;
+        }
+
+        lines.extend((index..from.end).map(Line::Context));
+
+        rtn.push(Hunk {
+            from,
+            to,
+            lines,
+        });
+    }
+
+    rtn
+}
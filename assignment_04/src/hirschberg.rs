@@ -0,0 +1,273 @@
+use crate::{Cost, Costs, EditKind};
+use crate::matrix::Substitution;
+
+/// a single step through the grid when walking back from the end
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Step {
+    Diagonal,
+    /// moves back one column
+    Horizontal,
+    /// moves back one row
+    Vertical,
+}
+
+/// the edit distance grid oriented so that the shorter input runs along the
+/// rows. only a single column of the grid is ever calculated at a time
+struct Grid<'a, T> {
+    rows: &'a [T],
+    cols: &'a [T],
+    vertical: Cost,
+    horizontal: Cost,
+    subs: &'a dyn Substitution<T>,
+    /// the order that steps are preferred in when more than one of them is
+    /// optimal, which always starts with the diagonal
+    priority: [Step; 3],
+}
+
+impl<T: Eq> Grid<'_, T> {
+    fn diagonal_cost(&self, row: usize, col: usize) -> Cost {
+        // substitutions are symmetric so transposing the grid never
+        // changes the cost
+        let sub = self.subs.value(&self.rows[row - 1], &self.cols[col - 1]);
+
+        if self.rows[row - 1] == self.cols[col - 1] {
+            sub.min(0)
+        } else {
+            sub
+        }
+    }
+
+    /// the first column of an area with `len` rows below its top row, where
+    /// every cell is reached by moving down from the corner
+    fn first_column(&self, len: usize) -> Vec<Cost> {
+        (0..=len).map(|row| row as Cost * self.vertical).collect()
+    }
+
+    /// calculates column `col` in to `current` from the column before it,
+    /// storing the step that the walk back would take from each cell in
+    /// `steps`. both columns start at `first_row` and cells above it are
+    /// never used so the top cell can only be reached from the left
+    fn next_column(&self, prev: &[Cost], first_row: usize, col: usize, current: &mut Vec<Cost>, steps: &mut Vec<Step>) {
+        current.clear();
+        steps.clear();
+
+        current.push(prev[0] + self.horizontal);
+        steps.push(Step::Horizontal);
+
+        for index in 1..prev.len() {
+            let diagonal = prev[index - 1] + self.diagonal_cost(first_row + index, col);
+            let horizontal = prev[index] + self.horizontal;
+            let vertical = current[index - 1] + self.vertical;
+
+            let mut best = diagonal;
+            let mut step = Step::Diagonal;
+
+            for check in &self.priority[1..] {
+                let value = match check {
+                    Step::Horizontal => horizontal,
+                    _ => vertical,
+                };
+
+                if value < best {
+                    best = value;
+                    step = *check;
+                }
+            }
+
+            current.push(best);
+            steps.push(step);
+        }
+    }
+
+    /// calculates every column of the area from `start` to `end`, returning
+    /// the values of column `end`
+    fn sweep(&self, first_row: usize, last_row: usize, start: usize, end: usize) -> Vec<Cost> {
+        let mut prev = self.first_column(last_row - first_row);
+        let mut current = Vec::with_capacity(prev.len());
+        let mut steps = Vec::with_capacity(prev.len());
+
+        for col in (start + 1)..=end {
+            self.next_column(&prev, first_row, col, &mut current, &mut steps);
+            std::mem::swap(&mut prev, &mut current);
+        }
+
+        prev
+    }
+
+    /// finds the row where the walk back from the bottom right corner of the
+    /// area first reaches column `mid`
+    ///
+    /// the columns after `mid` are calculated forwards while keeping, for
+    /// every cell, the row that the walk back from that cell would reach
+    /// column `mid` at. each cell takes the row of the cell that its step
+    /// leads to, so only two columns of rows are kept.
+    fn crossing(&self, first_row: usize, last_row: usize, start: usize, mid: usize, end: usize) -> usize {
+        let mut prev = self.sweep(first_row, last_row, start, mid);
+        let mut current = Vec::with_capacity(prev.len());
+        let mut steps = Vec::with_capacity(prev.len());
+
+        let mut rows: Vec<usize> = (0..prev.len()).collect();
+        let mut next_rows = Vec::with_capacity(prev.len());
+
+        for col in (mid + 1)..=end {
+            self.next_column(&prev, first_row, col, &mut current, &mut steps);
+            next_rows.clear();
+
+            for (index, step) in steps.iter().enumerate() {
+                let row = match step {
+                    Step::Diagonal => rows[index - 1],
+                    Step::Horizontal => rows[index],
+                    Step::Vertical => next_rows[index - 1],
+                };
+
+                next_rows.push(row);
+            }
+
+            std::mem::swap(&mut prev, &mut current);
+            std::mem::swap(&mut rows, &mut next_rows);
+        }
+
+        first_row + rows[last_row - first_row]
+    }
+
+    /// walks back through an area with a single column from its bottom right
+    /// corner to its top left corner
+    fn walk_column(&self, first_row: usize, last_row: usize, start: usize, steps: &mut Vec<Step>) {
+        let prev = self.first_column(last_row - first_row);
+        let mut current = Vec::with_capacity(prev.len());
+        let mut column_steps = Vec::with_capacity(prev.len());
+        self.next_column(&prev, first_row, start + 1, &mut current, &mut column_steps);
+
+        let mut index = last_row - first_row;
+
+        loop {
+            let step = column_steps[index];
+            steps.push(step);
+
+            match step {
+                Step::Diagonal => {
+                    index -= 1;
+                    break;
+                },
+                Step::Horizontal => break,
+                Step::Vertical => index -= 1,
+            }
+        }
+
+        // the first column is only reached by moving down from the corner
+        steps.extend(std::iter::repeat_n(Step::Vertical, index));
+    }
+
+    /// walks back from the bottom right corner of the area to its top left
+    /// corner, pushing each step as the walk takes it so they end up in
+    /// reverse order
+    ///
+    /// this is hirschberg's method. the row where the walk crosses the
+    /// middle column splits the area in to two smaller areas that are each
+    /// walked the same way. every split halves the columns so the total work
+    /// is about twice the size of the grid.
+    ///
+    /// the values in an area only count paths from its top left corner, but
+    /// that corner is on the walk back through the full grid so every cell of
+    /// the walk takes the same step as it would in the full grid.
+    fn walk(&self, first_row: usize, last_row: usize, start: usize, end: usize, steps: &mut Vec<Step>) {
+        match end - start {
+            0 => {
+                steps.extend(std::iter::repeat_n(Step::Vertical, last_row - first_row));
+            },
+            1 => {
+                self.walk_column(first_row, last_row, start, steps);
+            },
+            _ => {
+                let mid = start + (end - start) / 2;
+                let row = self.crossing(first_row, last_row, start, mid, end);
+
+                self.walk(row, last_row, mid, end, steps);
+                self.walk(first_row, row, start, mid, steps);
+            },
+        }
+    }
+}
+
+/// finds the minimum edit distance between the two slices along with the
+/// edits needed, without keeping the full grid in memory
+///
+/// the result is the same as following the edit stored in each cell of the
+/// full grid from `edit_distance`, preferring a match or substitution, then
+/// a deletion and then an insertion when more than one of them is optimal.
+/// the shorter input is placed along the rows and the columns are split in
+/// half at every level, so only O(min(n, m)) values are stored at a time.
+///
+/// transpositions are not supported so the transposition cost is ignored
+pub fn edit_distance<T: Eq>(from: &[T], to: &[T], costs: &Costs, subs: &dyn Substitution<T>) -> (Cost, Vec<EditKind>) {
+    // deletions move back along from and insertions move back along to
+    let transposed = to.len() > from.len();
+
+    let grid = if transposed {
+        Grid {
+            rows: from,
+            cols: to,
+            vertical: costs.del,
+            horizontal: costs.ins,
+            subs,
+            priority: [Step::Diagonal, Step::Vertical, Step::Horizontal],
+        }
+    } else {
+        Grid {
+            rows: to,
+            cols: from,
+            vertical: costs.ins,
+            horizontal: costs.del,
+            subs,
+            priority: [Step::Diagonal, Step::Horizontal, Step::Vertical],
+        }
+    };
+
+    let mut steps = Vec::with_capacity(grid.rows.len() + grid.cols.len());
+    grid.walk(0, grid.rows.len(), 0, grid.cols.len(), &mut steps);
+
+    steps.reverse();
+
+    // the walk only follows optimal steps so the cost is the sum of them
+    let mut cost = 0;
+    let mut edits = Vec::with_capacity(steps.len());
+    let mut from_index = 0;
+    let mut to_index = 0;
+
+    for step in steps {
+        let kind = match (step, transposed) {
+            (Step::Diagonal, _) => {
+                let sub = subs.value(&from[from_index], &to[to_index]);
+
+                let kind = if from[from_index] == to[to_index] {
+                    cost += sub.min(0);
+                    EditKind::Mat
+                } else {
+                    cost += sub;
+                    EditKind::Sub
+                };
+
+                from_index += 1;
+                to_index += 1;
+
+                kind
+            },
+            (Step::Horizontal, false) | (Step::Vertical, true) => {
+                from_index += 1;
+                cost += costs.del;
+
+                EditKind::Del
+            },
+            (Step::Horizontal, true) | (Step::Vertical, false) => {
+                to_index += 1;
+                cost += costs.ins;
+
+                EditKind::Ins
+            },
+        };
+
+        edits.push(kind);
+    }
+
+    (cost, edits)
+}
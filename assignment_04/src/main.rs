@@ -1,5 +1,20 @@
+mod subsequence;
+mod bitonic;
+mod hirschberg;
+mod affine;
+mod alignment;
+mod matrix;
+mod myers;
+mod diff;
+mod optimal;
+mod tokens;
+
 use std::str::FromStr;
 
+use subsequence::Direction;
+use tokens::Segmentation;
+use matrix::{Substitution, SubstitutionMatrix};
+
 fn parse_line_fill<T>(line: &str, list: &mut Vec<T>) -> bool
 where
     T: FromStr
@@ -34,597 +49,746 @@ where
     }
 }
 
-fn longest_increasing_subsequence(list: &[i32]) -> (usize, Vec<usize>) {
-    if list.is_empty() {
-        return (0, vec![]);
-    }
-
-    let mut max_len = 1;
-    let mut lengths = vec![1usize; list.len()];
-
-    for index in 1..list.len() {
-        let mut max = 0;
-
-        for subindex in 0..index {
-            if list[subindex] < list[index] && lengths[subindex] > max {
-                max = lengths[subindex];
-            }
-        }
-
-        max += 1;
+type Cost = i64;
 
-        if max > 1 {
-            if max > max_len {
-                max_len = max;
-            }
+/// the cost of each kind of edit
+struct Costs {
+    ins: Cost,
+    del: Cost,
+    sub: Cost,
+    /// swapping two adjacent tokens, None if transpositions are not allowed
+    tra: Option<Cost>,
+}
 
-            lengths[index] = max;
-        }
+/// every pair of tokens costs `sub`
+impl<T> Substitution<T> for Costs {
+    fn value(&self, _: &T, _: &T) -> Cost {
+        self.sub
     }
+}
 
-    (max_len, lengths)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EditKind {
+    Sub,
+    Ins,
+    Del,
+    Mat,
+    /// two adjacent tokens swapped, covering two tokens of each input
+    Tra,
 }
 
-fn longest_decreasing_subsequence(list: &[i32]) -> (usize, Vec<usize>) {
-    if list.is_empty() {
-        return (0, vec![]);
+impl std::fmt::Display for EditKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EditKind::Sub => write!(f, "s"),
+            EditKind::Ins => write!(f, "i"),
+            EditKind::Del => write!(f, "d"),
+            EditKind::Mat => write!(f, "m"),
+            EditKind::Tra => write!(f, "t"),
+        }
     }
+}
 
-    let mut max_len = 1;
-    let mut lengths = vec![1usize; list.len()];
-
-    for index in 1..list.len() {
-        let mut max = 0;
+/// a set of edit kinds stored as one bit for each kind
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct EditSet(u8);
 
-        for subindex in 0..index {
-            if list[subindex] > list[index] && lengths[subindex] > max {
-                max = lengths[subindex];
-            }
-        }
+impl EditSet {
+    const KINDS: [EditKind; 5] = [EditKind::Mat, EditKind::Sub, EditKind::Del, EditKind::Ins, EditKind::Tra];
 
-        max += 1;
+    fn of(kind: EditKind) -> Self {
+        EditSet(1 << kind as u8)
+    }
 
-        if max > 1 {
-            if max > max_len {
-                max_len = max;
-            }
+    fn insert(&mut self, kind: EditKind) {
+        self.0 |= 1 << kind as u8;
+    }
 
-            lengths[index] = max;
-        }
+    fn contains(&self, kind: EditKind) -> bool {
+        self.0 & (1 << kind as u8) != 0
     }
 
-    (max_len, lengths)
+    /// the kinds in the set in a fixed order
+    fn iter(self) -> impl DoubleEndedIterator<Item = EditKind> {
+        Self::KINDS.into_iter().filter(move |kind| self.contains(*kind))
+    }
 }
 
-struct Calced {
-    max_len: usize,
-    calced: Vec<usize>,
+#[derive(Clone)]
+struct Edit {
+    value: Cost,
+    /// the edit followed by the traceback
+    kind: EditKind,
+    /// every edit that reaches the cell with the same value
+    optimal: EditSet,
 }
 
-fn longest_inc_dsc_subsequence(list: &[i32]) -> (Calced, Calced) {
-    let mut lis_max_len = 1;
-    let mut lds_max_len = 1;
-    let mut lis_lengths = vec![1usize; list.len()];
-    let mut lds_lengths = vec![1usize; list.len()];
-
-    for index in 1..list.len() {
-        let mut lis_max = 0;
-        let mut lds_max = 0;
-
-        for subindex in 0..index {
-            if list[subindex] < list[index] && lis_lengths[subindex] > lis_max {
-                lis_max = lis_lengths[subindex];
-            }
-
-            if list[subindex] > list[index] && lds_lengths[subindex] > lds_max {
-                lds_max = lds_lengths[subindex];
-            }
-        }
-
-        lis_max += 1;
-        lds_max += 1;
+impl Edit {
+    fn new(value: Cost, kind: EditKind) -> Self {
+        Edit { value, kind, optimal: EditSet::of(kind) }
+    }
 
-        if lis_max > 1 {
-            if lis_max > lis_max_len {
-                lis_max_len = lis_max;
-            }
+    fn sub(value: Cost) -> Self {
+        Edit::new(value, EditKind::Sub)
+    }
 
-            lis_lengths[index] = lis_max;
-        }
+    fn ins(value: Cost) -> Self {
+        Edit::new(value, EditKind::Ins)
+    }
 
-        if lds_max > 1 {
-            if lds_max > lds_max_len {
-                lds_max_len = lds_max;
-            }
+    fn del(value: Cost) -> Self {
+        Edit::new(value, EditKind::Del)
+    }
 
-            lds_lengths[index] = lds_max;
-        }
+    fn mat(value: Cost) -> Self {
+        Edit::new(value, EditKind::Mat)
     }
 
-    (Calced {
-        max_len: lis_max_len,
-        calced: lis_lengths,
-    },
-    Calced {
-        max_len: lds_max_len,
-        calced: lds_lengths,
-    })
+    fn tra(value: Cost) -> Self {
+        Edit::new(value, EditKind::Tra)
+    }
 }
 
-fn longest_bitonic_subsequence(list: &[i32]) -> usize {
-    let start = std::time::Instant::now();
-
-    let mut bitonic_max = 0;
-
-    //println!("calculating increasing bitonic");
-
-    {
-        let (_lis_max, lis_calced) = longest_increasing_subsequence(list);
-
-        for index in 0..list.len() {
-            let (lds_max, _lds_calced) = longest_decreasing_subsequence(&list[index..list.len()]);
-
-            let summed = lds_max + lis_calced[index] - 1;
-
-            //println!("index: {}\n{:?} {} | {:?} {}", index, &list[0..=index], lis_calced[index], &list[index..list.len()], lds_max);
-            //println!("{:?} | {:?}", &lis_calced[0..=index], lds_calced);
-            //println!("{} + {} - 1 = {}", lds_max, lis_calced[index], summed);
-
-            if summed > bitonic_max {
-                bitonic_max = summed;
-            }
-        }
+impl std::fmt::Display for Edit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{}", self.value, self.kind)
     }
+}
 
-    //println!("calculating decreasing bitonic");
-
-    {
-        let (_lds_max, lds_calced) = longest_decreasing_subsequence(list);
-
-        for index in 0..list.len() {
-            let (lis_max, _lis_calced) = longest_increasing_subsequence(&list[index..list.len()]);
+struct EditResult {
+    result: Vec<Vec<Edit>>,
+    longest: usize,
+}
 
-            let summed = lis_max + lds_calced[index] - 1;
+/// finds the minimum edit distance between the two slices
+///
+/// when transpositions are allowed two adjacent tokens that are swapped can
+/// be replaced with a single edit. a token can only be part of one edit so
+/// a transposed pair is never edited again.
+///
+/// grid layout
+///     =   f   r   o   m
+///   +---+---+---+---+---+
+/// = |   |   |   |   |   |
+///   +---+---+---+---+---+
+/// t |   |   |   |   |   |
+///   +---+---+---+---+---+
+/// o |   |   |   |   |   |
+///   +---+---+---+---+---+
+fn edit_distance<T: Eq>(
+    from: &[T],
+    to: &[T],
+    costs: &Costs,
+    subs: &dyn Substitution<T>,
+    verbose: bool
+) -> EditResult {
+    let mut longest = 0;
+    let from_len = from.len() + 1;
+    let to_len = to.len() + 1;
 
-            //println!("index: {}\n{:?} {} | {:?} {}", index, &list[0..=index], lds_calced[index], &list[index..list.len()], lis_max);
-            //println!("{:?} | {:?}", &lds_calced[0..=index], lis_calced);
-            //println!("{} + {} - 1 = {}", lis_max, lds_calced[index], summed);
+    let mut memory = vec![vec![Edit::mat(0); from_len]; to_len];
 
-            if summed > bitonic_max {
-                bitonic_max = summed;
-            }
-        }
+    for to_index in 1..to_len {
+        memory[to_index][0] = Edit::ins(memory[to_index - 1][0].value + costs.ins);
     }
 
-    let duration = start.elapsed();
-
-    println!("1 duration: {:?}", duration);
-
-    let start = std::time::Instant::now();
+    for from_index in 1..from_len {
+        memory[0][from_index] = Edit::del(memory[0][from_index - 1].value + costs.del);
+    }
 
-    let mut test_bitonic_max = 0;
+    for from_index in 1..from_len {
+        if verbose {
+            println!("==============================");
+        }
 
-    {
-        let (lis_base, lds_base) = longest_inc_dsc_subsequence(list);
+        for to_index in 1..to_len {
+            if verbose {
+                print!("{from_index}:{to_index}");
+            }
 
-        for index in 0..list.len() {
-            let (sub_lis, sub_lds) = longest_inc_dsc_subsequence(&list[index..list.len()]);
+            let curr_sub = memory[to_index - 1][from_index - 1].value +
+                subs.value(&from[from_index - 1], &to[to_index - 1]);
+            let curr_del = memory[to_index][from_index - 1].value + costs.del;
+            let curr_ins = memory[to_index - 1][from_index].value + costs.ins;
 
-            let lis_sum = sub_lds.max_len + lis_base.calced[index] - 1;
-            let lds_sum = sub_lis.max_len + lds_base.calced[index] - 1;
+            // matching tokens only have one diagonal edit so that the same
+            // alignment is never recorded twice
+            let diagonal = if from[from_index - 1] == to[to_index - 1] {
+                let mut min = Edit::mat(memory[to_index - 1][from_index - 1].value);
 
-            if lis_sum > lds_sum {
-                if lis_sum > test_bitonic_max {
-                    test_bitonic_max = lis_sum;
-                }
-            } else {
-                if lds_sum > test_bitonic_max {
-                    test_bitonic_max = lds_sum;
+                if curr_sub < min.value {
+                    min = Edit::sub(curr_sub);
                 }
-            }
-        }
-    }
 
-    let duration = start.elapsed();
-
-    println!("2 duration: {:?}", duration);
+                min
+            } else {
+                Edit::sub(curr_sub)
+            };
 
-    let start = std::time::Instant::now();
+            let diagonal_value = diagonal.value;
+            let diagonal_kind = diagonal.kind;
+            let mut curr_tra = None;
+            let mut min = diagonal;
 
-    let mut bitonic_max = 0;
-    let rev_len = list.len() - 1;
+            if curr_del < min.value {
+                min = Edit::del(curr_del);
+            }
 
-    let mut ins_bit_lens = vec![(0usize, 0usize); list.len()];
-    let mut dsc_bit_lens = vec![(0usize, 0usize); list.len()];
-    ins_bit_lens[0].0 = 1;
-    ins_bit_lens[rev_len].1 = 1;
-    dsc_bit_lens[0].0 = 1;
-    dsc_bit_lens[rev_len].1 = 1;
+            if curr_ins < min.value {
+                min = Edit::ins(curr_ins);
+            }
 
-    for index in 1..list.len() {
-        let rev_index = rev_len - index;
+            if let Some(tra) = costs.tra {
+                if from_index > 1 && to_index > 1 &&
+                    from[from_index - 1] == to[to_index - 2] &&
+                    from[from_index - 2] == to[to_index - 1]
+                {
+                    let value = memory[to_index - 2][from_index - 2].value + tra;
 
-        for subindex in 0..index {
-            let rev_subindex = rev_len - subindex;
+                    if value < min.value {
+                        min = Edit::tra(value);
+                    }
 
-            // increasing bitonic
-            if list[subindex] < list[index] && ins_bit_lens[subindex].0 > ins_bit_lens[index].0 {
-                ins_bit_lens[index].0 = ins_bit_lens[subindex].0;
+                    curr_tra = Some(value);
+                }
             }
 
-            if list[rev_subindex] < list[rev_index] && ins_bit_lens[rev_subindex].1 > ins_bit_lens[rev_index].1 {
-                ins_bit_lens[rev_index].1 = ins_bit_lens[rev_subindex].1;
-            }
+            let candidates = [
+                (diagonal_value, diagonal_kind),
+                (curr_del, EditKind::Del),
+                (curr_ins, EditKind::Ins),
+            ];
 
-            // decreasing bitonic
-            if list[subindex] > list[index] && dsc_bit_lens[subindex].0 > dsc_bit_lens[index].0 {
-                dsc_bit_lens[index].0 = dsc_bit_lens[subindex].0;
+            for (value, kind) in candidates {
+                if value == min.value {
+                    min.optimal.insert(kind);
+                }
             }
 
-            if list[rev_subindex] > list[rev_index] && dsc_bit_lens[rev_subindex].1 > dsc_bit_lens[rev_index].1 {
-                dsc_bit_lens[rev_index].1 = dsc_bit_lens[rev_subindex].1;
+            if curr_tra == Some(min.value) {
+                min.optimal.insert(EditKind::Tra);
             }
-        }
 
-        ins_bit_lens[index].0 += 1;
-        ins_bit_lens[rev_index].1 += 1;
-        dsc_bit_lens[index].0 += 1;
-        dsc_bit_lens[rev_index].1 += 1;
+            // for printing
+            if verbose {
+                if min.value == 0 {
+                    if 1 > longest {
+                        longest = 1;
+                    }
+                } else if min.value < 0 {
+                    let check = min.value.abs().ilog10() + 2;
 
-        if index == rev_index {
-            let lis_value = ins_bit_lens[index].0 + ins_bit_lens[index].1 - 1;
-            let lds_value = dsc_bit_lens[index].0 + dsc_bit_lens[index].1 - 1;
+                    if check > longest {
+                        longest = check;
+                    }
+                } else {
+                    let check = min.value.ilog10() + 1;
 
-            if lis_value > lds_value {
-                if lis_value > bitonic_max {
-                    bitonic_max = lis_value;
-                }
-            } else {
-                if lds_value > bitonic_max {
-                    bitonic_max = lds_value;
+                    if check > longest {
+                        longest = check;
+                    }
                 }
+
+                println!(" -> {min}");
             }
-        } else if index > rev_index {
-            bitonic_max = [
-                ins_bit_lens[index].0 + ins_bit_lens[index].1 - 1,
-                ins_bit_lens[rev_index].0 + ins_bit_lens[rev_index].1 - 1,
-                dsc_bit_lens[index].0 + dsc_bit_lens[index].1 - 1,
-                dsc_bit_lens[rev_index].0 + dsc_bit_lens[rev_index].1 - 1,
-                bitonic_max
-            ].into_iter().max().unwrap();
+
+            memory[to_index][from_index] = min;
         }
     }
 
-    let duration = start.elapsed();
-
-    println!("3 duration: {:?}", duration);
+    if verbose {
+        println!("==============================");
+    }
 
-    bitonic_max
+    EditResult {
+        result: memory,
+        longest: longest as usize,
+    }
 }
 
-fn lis_lds_main() {
-    /*
-    let list = [
-        ((3, 3), vec![2,7,4,3,8]),
-        ((4, 2), vec![2,4,3,7,4,5]),
-    ];
+/// the options used when finding the edit distance
+struct EditOptions {
+    /// use the linear space hirschberg method instead of the full grid
+    linear_space: bool,
+    /// charge a gap open cost for every run of insertions or deletions
+    affine: bool,
+    /// find a local or semi-global alignment instead of the edit distance
+    scope: Option<alignment::Scope>,
+    /// costs for substituting each pair of tokens instead of a single cost
+    matrix: Option<SubstitutionMatrix>,
+    segmentation: Segmentation,
+    /// what is placed in the alignment for each inserted or deleted token
+    gap: String,
+    /// list every optimal alignment up to `limit` instead of just one
+    all_optimal: bool,
+    limit: usize,
+    verbose: bool,
+}
 
-    for test in list {
-        let lens = test.0;
+/// the different modes that the program can run in
+enum Mode {
+    EditDistance,
+    Subsequence(Direction),
+    Bitonic,
+    /// a line diff of the two files
+    Diff(String, String),
+}
 
-        let (lis_max, _lis_calced) = longest_increasing_subsequence(&test.1);
+fn main() {
+    let mut verbose = false;
+    let mut mode = Mode::EditDistance;
+    let mut strict = true;
+    let mut linear_space = false;
+    let mut affine = false;
+    let mut scope = None;
+    let mut matrix = None;
+    let mut context = 3;
+    let mut all_optimal = false;
+    let mut limit = 100;
+    let mut segmentation = Segmentation::Chars;
+    let mut gap = String::from("_");
+    let mut args = std::env::args();
+    args.next();
 
-        let (lds_max, _lds_calced) = longest_decreasing_subsequence(&test.1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--verbose" => {
+                verbose = true;
+            },
+            "--lis" => {
+                mode = Mode::Subsequence(Direction::Increasing);
+            },
+            "--lds" => {
+                mode = Mode::Subsequence(Direction::Decreasing);
+            },
+            "--bitonic" => {
+                mode = Mode::Bitonic;
+            },
+            "--hirschberg" => {
+                linear_space = true;
+            },
+            "--affine" => {
+                affine = true;
+            },
+            "--local" => {
+                scope = Some(alignment::Scope::Local);
+            },
+            "--semi-global" => {
+                scope = Some(alignment::Scope::SemiGlobal);
+            },
+            "--non-strict" => {
+                strict = false;
+            },
+            "--graphemes" => {
+                segmentation = Segmentation::Graphemes;
+            },
+            "--matrix" => {
+                let Some(path) = args.next() else {
+                    panic!("missing file path after --matrix");
+                };
+
+                let text = match std::fs::read_to_string(&path) {
+                    Ok(text) => text,
+                    Err(err) => panic!("failed to read substitution matrix \"{path}\": {err}"),
+                };
+
+                matrix = match SubstitutionMatrix::parse(&text) {
+                    Ok(parsed) => Some(parsed),
+                    Err(err) => panic!("invalid substitution matrix: {err}"),
+                };
+            },
+            "--diff" => {
+                let (Some(from), Some(to)) = (args.next(), args.next()) else {
+                    panic!("--diff needs the paths of the two files to compare");
+                };
+
+                mode = Mode::Diff(from, to);
+            },
+            "--all-optimal" => {
+                all_optimal = true;
+            },
+            "--limit" => {
+                let Some(value) = args.next() else {
+                    panic!("missing amount of alignments after --limit");
+                };
+
+                let Ok(parsed) = value.parse() else {
+                    panic!("invalid alignment limit: \"{value}\"");
+                };
+
+                limit = parsed;
+            },
+            "--context" => {
+                let Some(value) = args.next() else {
+                    panic!("missing amount of lines after --context");
+                };
+
+                let Ok(parsed) = value.parse() else {
+                    panic!("invalid amount of context lines: \"{value}\"");
+                };
+
+                context = parsed;
+            },
+            "--gap" => {
+                let Some(symbol) = args.next() else {
+                    panic!("missing gap symbol after --gap");
+                };
+
+                if symbol.is_empty() {
+                    panic!("gap symbol can not be empty");
+                }
 
-        assert_eq!(lis_max, lens.0);
-        assert_eq!(lds_max, lens.1);
+                gap = symbol;
+            },
+            _ => {}
+        }
     }
 
-    let bitonic = [
-        (7, vec![1,2,3,4,3,2,1]),
-        (5, vec![5,7,3,2,8,5,5,2]),
-        (5, vec![3,1,7,8,8,4,10,23]),
-        (0, vec![5,8,8,3,4,1,7,-3,2,9,12]),
-        (0, vec![1,11,2,10,4,5,2,1]),
-        (0, vec![1,2,5,3,2]),
-        (0, vec![4,5,9,7,6,3,1]),
-    ];
-
-    for test in bitonic {
-        println!("bit: {} {:?}", test.0, test.1);
+    let mut lines = std::io::stdin().lines();
 
-        let max = longest_bitonic_subsequence(&test.1);
+    match mode {
+        Mode::EditDistance => {
+            let options = EditOptions {
+                linear_space,
+                affine,
+                scope,
+                matrix,
+                segmentation,
+                gap,
+                all_optimal,
+                limit,
+                verbose,
+            };
 
-        println!("max: {}", max);
+            edit_distance_main(&mut lines, &options)
+        },
+        Mode::Subsequence(direction) => subsequence_main(&mut lines, direction, strict),
+        Mode::Bitonic => bitonic_main(&mut lines, strict),
+        Mode::Diff(from, to) => diff_main(&from, &to, context),
     }
-    */
-
-    let check =     [5,8,8,3,4,1,7,-3,2,9,12];
-    let check_rev = [12,9,2,-3,7,1,4,3,8,8,5];
-    let mut lis_sum = [0,0,0,0,0,0,0,0,0,0,0];
-    let mut lds_sum = [0,0,0,0,0,0,0,0,0,0,0];
-
-    print!("   base:");
+}
 
-    for v in check {
-        print!(" {:2}", v);
-    }
+/// reads a list of values from each remaining line, skipping blank lines
+fn read_sequence<I>(lines: &mut I) -> Option<Vec<i32>>
+where
+    I: Iterator<Item = std::io::Result<String>>
+{
+    for line in lines {
+        let valid = line.expect("failed to read input from stdin");
 
-    print!("\n\n    lis:");
+        if valid.trim().is_empty() {
+            continue;
+        }
 
-    let (lis_max, lis_calced) = longest_increasing_subsequence(&check);
+        let Some(rtn) = parse_line::<i32>(valid.trim()) else {
+            panic!("invalid sequence provided: \"{}\"", valid);
+        };
 
-    for index in 0..lis_calced.len() {
-        print!(" {:2}", lis_calced[index]);
-        lis_sum[index] += lis_calced[index];
+        return Some(rtn);
     }
 
-    print!("\nrev lis:");
-
-    let (lis_max, mut lis_calced) = longest_increasing_subsequence(&check_rev);
-    lis_calced.reverse();
-
-    for index in 0..lis_calced.len() {
-        print!(" {:2}", lis_calced[index]);
-        lis_sum[index] += lis_calced[index];
-    }
+    None
+}
 
-    print!("\n    sum:");
+fn print_list<T: std::fmt::Display>(name: &str, list: &[T]) {
+    print!("{name}:");
 
-    for v in lis_sum {
-        print!(" {:2}", v - 1);
+    for value in list {
+        print!(" {value}");
     }
 
-    print!("\n\n    lds:");
+    println!();
+}
 
-    let (lds_max, lds_calced) = longest_decreasing_subsequence(&check);
+fn subsequence_main<I>(lines: &mut I, direction: Direction, strict: bool)
+where
+    I: Iterator<Item = std::io::Result<String>>
+{
+    while let Some(list) = read_sequence(lines) {
+        let found = subsequence::longest(&list, direction, strict);
 
-    for index in 0..lds_calced.len() {
-        print!(" {:2}", lds_calced[index]);
-        lds_sum[index] += lds_calced[index];
+        println!("length: {}", found.len());
+        print_list("indices", &found.indices);
+        print_list("values", &found.values);
     }
+}
 
-    print!("\nrev lds:");
-
-    let (lds_max, mut lds_calced) = longest_decreasing_subsequence(&check_rev);
-    lds_calced.reverse();
+fn bitonic_main<I>(lines: &mut I, strict: bool)
+where
+    I: Iterator<Item = std::io::Result<String>>
+{
+    while let Some(list) = read_sequence(lines) {
+        for shape in [bitonic::Shape::Peak, bitonic::Shape::Valley] {
+            let found = bitonic::longest(&list, shape, strict);
 
-    for index in 0..lds_calced.len() {
-        print!(" {:2}", lds_calced[index]);
-        lds_sum[index] += lds_calced[index];
+            println!("{shape}: {}", found.len());
+            print_list("indices", &found.indices);
+            print_list("values", &found.values);
+        }
     }
+}
 
-    print!("\n    sum:");
-
-    for v in lds_sum {
-        print!(" {:2}", v - 1);
+/// creates the two lines of an alignment from the edits that turn from in
+/// to to. each inserted or deleted token is shown with the gap symbol
+fn build_alignment(from: &[&str], to: &[&str], edits: &[EditKind], gap: &str) -> (String, String) {
+    let mut from_output = String::new();
+    let mut to_output = String::new();
+    let mut from_iter = from.iter();
+    let mut to_iter = to.iter();
+
+    for kind in edits {
+        match kind {
+            EditKind::Mat | EditKind::Sub => {
+                from_output.push_str(from_iter.next().unwrap());
+                to_output.push_str(to_iter.next().unwrap());
+            },
+            EditKind::Ins => {
+                from_output.push_str(gap);
+                to_output.push_str(to_iter.next().unwrap());
+            },
+            EditKind::Del => {
+                from_output.push_str(from_iter.next().unwrap());
+                to_output.push_str(gap);
+            },
+            EditKind::Tra => {
+                for _ in 0..2 {
+                    from_output.push_str(from_iter.next().unwrap());
+                    to_output.push_str(to_iter.next().unwrap());
+                }
+            },
+        }
     }
 
-    println!("");
+    (from_output, to_output)
 }
 
-fn min_index(list: &[usize]) -> usize {
-    if list.is_empty() {
-        return 0;
-    }
+/// prints every cell of the grid along with the tokens of each input
+fn print_grid<T: std::fmt::Display>(from: &[T], to: &[T], result: &[Vec<Edit>], longest: usize) {
+    let leading_width = (to.len().ilog10() + 1) as usize;
 
-    let mut curr = 0;
+    let dash_spacer = "-".repeat(longest);
+    let spacer = " ".repeat(longest);
+    let leading_dash_spacer = "-".repeat(leading_width);
+    let leading_spacer = " ".repeat(leading_width);
 
-    for index in 1..list.len() {
-        if list[curr] > list[index] {
-            curr = index;
-        }
-    }
+    print!(" {leading_spacer}    |");
 
-    curr
-}
-
-type Cost = i64;
+    for col in 0..=from.len() {
+        print!(" {col:longest$} ");
+    }
 
-#[derive(Clone)]
-enum EditKind {
-    Sub,
-    Ins,
-    Del,
-    Mat,
-}
+    println!();
+    print!(" {leading_spacer}    |");
 
-impl std::fmt::Display for EditKind {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            EditKind::Sub => write!(f, "{}", 's'),
-            EditKind::Ins => write!(f, "{}", 'i'),
-            EditKind::Del => write!(f, "{}", 'd'),
-            EditKind::Mat => write!(f, "{}", 'm'),
+    for index in 0..=from.len() {
+        if index == 0 {
+            print!(" {spacer} ");
+        } else {
+            print!(" {:>longest$} ", from[index - 1]);
         }
     }
-}
 
-#[derive(Clone)]
-struct Edit {
-    value: Cost,
-    kind: EditKind,
-}
+    println!();
+    print!("-{leading_dash_spacer}----+");
 
-impl Edit {
-    fn sub(value: Cost) -> Self {
-        Edit { value, kind: EditKind::Sub }
+    for _ in 0..=from.len() {
+        print!("-{dash_spacer}-");
     }
 
-    fn ins(value: Cost) -> Self {
-        Edit { value, kind: EditKind::Ins }
-    }
+    println!();
 
-    fn del(value: Cost) -> Self {
-        Edit { value, kind: EditKind::Del }
-    }
+    for (index, row) in result.iter().enumerate() {
+        if index == 0 {
+            print!(" {index:leading_width$}    |");
+        } else {
+            print!(" {index:leading_width$}  {} |", to[index - 1]);
+        }
 
-    fn mat(value: Cost) -> Self {
-        Edit { value, kind: EditKind::Mat }
-    }
-}
+        for pair in row {
+            print!(" {:longest$}{}", pair.value, pair.kind);
+        }
 
-impl std::fmt::Display for Edit {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}{}", self.value, self.kind)
+        println!();
     }
 }
 
-struct EditResult {
-    result: Vec<Vec<Edit>>,
-    longest: usize,
+/// the edits found by following the grid back from a cell
+struct Traceback {
+    /// the edits in the order that they are applied
+    edits: Vec<EditKind>,
+    /// the cell that the walk stopped at
+    from_index: usize,
+    to_index: usize,
 }
 
-/// finds the minimum edit distance between the two slices
-///
-/// grid layout
-///     =   f   r   o   m
-///   +---+---+---+---+---+
-/// = |   |   |   |   |   |
-///   +---+---+---+---+---+
-/// t |   |   |   |   |   |
-///   +---+---+---+---+---+
-/// o |   |   |   |   |   |
-///   +---+---+---+---+---+
-fn edit_distance(from: &[u8], to: &[u8], ins: Cost, del: Cost, sub: Cost, verbose: bool) -> EditResult {
-    let mut longest = 0;
-    let from_len = from.len() + 1;
-    let to_len = to.len() + 1;
-
-    let mut memory = vec![vec![Edit::mat(0); from_len]; to_len];
-
-    for to_index in 1..to_len {
-        memory[to_index][0] = Edit::ins(memory[to_index - 1][0].value + ins);
-    }
-
-    for from_index in 1..from_len {
-        memory[0][from_index] = Edit::del(memory[0][from_index - 1].value + del);
-    }
+/// follows the edit stored in each cell back from the given cell until
+/// `stop` is true for the cell reached
+fn traceback<F>(result: &[Vec<Edit>], mut from_index: usize, mut to_index: usize, stop: F, verbose: bool) -> Traceback
+where
+    F: Fn(&Edit, usize, usize) -> bool
+{
+    let mut edits = Vec::new();
 
-    for from_index in 1..from_len {
+    while !stop(&result[to_index][from_index], from_index, to_index) {
         if verbose {
-            println!("==============================");
+            print!("indexs: {from_index}:{to_index}");
         }
 
-        for to_index in 1..to_len {
-            if verbose {
-                print!("{from_index}:{to_index}");
-            }
-
-            let curr_sub = memory[to_index - 1][from_index - 1].value + sub;
-            let curr_del = memory[to_index][from_index - 1].value + del;
-            let curr_ins = memory[to_index - 1][from_index].value + ins;
-
-            memory[to_index][from_index] = if from[from_index - 1] == to[to_index - 1] {
-                let mut min = Edit::mat(memory[to_index - 1][from_index - 1].value);
+        let kind = result[to_index][from_index].kind;
 
-                if curr_sub < min.value {
-                    min = Edit::sub(curr_sub);
-                }
-
-                if curr_del < min.value {
-                    min = Edit::del(curr_del);
+        match kind {
+            EditKind::Mat => {
+                if verbose {
+                    print!(" mat");
                 }
 
-                if curr_ins < min.value {
-                    min = Edit::ins(curr_ins);
+                from_index -= 1;
+                to_index -= 1;
+            }
+            EditKind::Sub => {
+                if verbose {
+                    print!(" sub");
                 }
 
-                // for printing
+                from_index -= 1;
+                to_index -= 1;
+            }
+            EditKind::Ins => {
                 if verbose {
-                    if min.value == 0 {
-                        if 1 > longest {
-                            longest = 1;
-                        }
-                    } else if min.value < 0 {
-                        let check = min.value.abs().ilog10() + 2;
-
-                        if check > longest {
-                            longest = check;
-                        }
-                    } else {
-                        let check = min.value.ilog10() + 1;
-
-                        if check > longest {
-                            longest = check;
-                        }
-                    }
-
-                    println!(" -> {min}");
+                    print!(" ins");
                 }
 
-                min
-            } else {
-                let mut min = Edit::sub(curr_sub);
-
-                if curr_del < min.value {
-                    min = Edit::del(curr_del);
+                to_index -= 1;
+            }
+            EditKind::Del => {
+                if verbose {
+                    print!(" del");
                 }
 
-                if curr_ins < min.value {
-                    min = Edit::ins(curr_ins);
+                from_index -= 1;
+            },
+            EditKind::Tra => {
+                if verbose {
+                    print!(" tra");
                 }
 
-                // for printing
-                if verbose {
-                    if min.value == 0 {
-                        if 1 > longest {
-                            longest = 1;
-                        }
-                    } else if min.value < 0 {
-                        let check = min.value.abs().ilog10() + 2;
-
-                        if check > longest {
-                            longest = check;
-                        }
-                    } else {
-                        let check = min.value.ilog10() + 1;
-
-                        if check > longest {
-                            longest = check;
-                        }
-                    }
+                from_index -= 2;
+                to_index -= 2;
+            },
+        }
 
-                    println!(" -> {min}");
-                }
+        edits.push(kind);
 
-                min
-            };
+        if verbose {
+            println!(" -> {from_index}:{to_index}");
         }
     }
 
+    edits.reverse();
+
+    Traceback {
+        edits,
+        from_index,
+        to_index,
+    }
+}
+
+/// finds the edit distance with the full grid and follows the edit stored
+/// in each cell back from the end
+fn full_edit_distance<T>(
+    from: &[T],
+    to: &[T],
+    costs: &Costs,
+    subs: &dyn Substitution<T>,
+    verbose: bool
+) -> (Cost, Vec<EditKind>)
+where
+    T: Eq + std::fmt::Display
+{
+    let rtn = edit_distance(from, to, costs, subs, verbose);
+    let result = rtn.result;
+
     if verbose {
-        println!("==============================");
+        print_grid(from, to, &result, rtn.longest);
     }
 
-    EditResult {
-        result: memory,
-        longest: longest as usize,
+    let edit_value = result[to.len()][from.len()].value;
+
+    // the first row and column only hold deletions and insertions
+    let found = traceback(&result, from.len(), to.len(), |_, from_index, to_index| {
+        from_index == 0 || to_index == 0
+    }, verbose);
+
+    let mut edits: Vec<EditKind> = std::iter::repeat_n(EditKind::Ins, found.to_index)
+        .chain(std::iter::repeat_n(EditKind::Del, found.from_index))
+        .collect();
+
+    edits.extend(found.edits);
+
+    (edit_value, edits)
+}
+
+/// uses the substitution matrix when one was given instead of the single
+/// value from the costs line
+fn substitution<'a, T: AsRef<str>>(
+    matrix: &'a Option<SubstitutionMatrix>,
+    single: &'a dyn Substitution<T>
+) -> &'a dyn Substitution<T> {
+    match matrix {
+        Some(matrix) => matrix,
+        None => single,
     }
 }
 
-fn main() {
-    let mut verbose = false;
-    let mut args = std::env::args();
-    args.next();
+/// gives every distinct token an id so that comparing two tokens is cheap
+fn token_ids<'a>(tokens: &[&'a str], ids: &mut std::collections::HashMap<&'a str, usize>) -> Vec<usize> {
+    tokens.iter()
+        .map(|token| {
+            let next = ids.len();
+            *ids.entry(*token).or_insert(next)
+        })
+        .collect()
+}
 
-    loop {
-        let Some(arg) = args.next() else {
-            break;
-        };
+/// looks up the substitution of the tokens that two ids were given to
+struct ById<'a> {
+    tokens: Vec<&'a str>,
+    subs: &'a dyn Substitution<&'a str>,
+}
+
+impl<'a> ById<'a> {
+    fn new(ids: &std::collections::HashMap<&'a str, usize>, subs: &'a dyn Substitution<&'a str>) -> Self {
+        let mut tokens = vec![""; ids.len()];
 
-        if arg == "--verbose" {
-            verbose = true;
+        for (token, id) in ids {
+            tokens[*id] = token;
+        }
+
+        ById {
+            tokens,
+            subs,
         }
     }
+}
 
-    let mut lines = std::io::stdin().lines();
-    let ins: Cost;
-    let del: Cost;
-    let sub: Cost;
+impl Substitution<usize> for ById<'_> {
+    fn value(&self, a: &usize, b: &usize) -> Cost {
+        self.subs.value(&self.tokens[*a], &self.tokens[*b])
+    }
+}
+
+fn edit_distance_main<I>(lines: &mut I, options: &EditOptions)
+where
+    I: Iterator<Item = std::io::Result<String>>
+{
+    let costs: Costs;
+    let mut gap_costs = None;
+    let mut scores = None;
 
     {
-        let total: usize = {
+        // the total is only checked to be a number. every line after the
+        // costs is read as a pair of strings
+        let _total: usize = {
             let check = lines.next()
                 .expect("no edit distance data specified")
                 .expect("failed to read input from stdin");
@@ -640,16 +804,61 @@ fn main() {
             .expect("missing edit weights")
             .expect("failed to read input from stdin");
 
-        let costs = parse_line::<Cost>(&costs_line)
+        let list = parse_line::<Cost>(&costs_line)
             .expect("failed to parse costs line. invalid intager characters providied");
 
-        if costs.len() != 3 {
-            panic!("invalid number of costs provided. expected 3");
+        if options.affine {
+            if list.len() != 4 {
+                panic!("invalid number of costs provided. expected 4 with affine gaps");
+            }
+
+            gap_costs = Some(affine::GapCosts {
+                ins: list[0],
+                del: list[1],
+                sub: list[2],
+                open: list[3],
+            });
+        } else if options.scope.is_some() {
+            if list.len() != 4 {
+                panic!("invalid number of costs provided. expected 4 with local or semi-global alignment");
+            }
+
+            scores = Some(alignment::Scores {
+                ins: list[0],
+                del: list[1],
+                sub: list[2],
+                mat: list[3],
+            });
+        } else if list.len() != 3 && list.len() != 4 {
+            panic!("invalid number of costs provided. expected 3 or 4");
         }
 
-        ins = costs[0];
-        del = costs[1];
-        sub = costs[2];
+        // the fourth value is only a transposition cost for the edit
+        // distance
+        let transpositions = !options.affine && options.scope.is_none();
+
+        costs = Costs {
+            ins: list[0],
+            del: list[1],
+            sub: list[2],
+            tra: if transpositions { list.get(3).copied() } else { None },
+        };
+    }
+
+    if options.linear_space && costs.tra.is_some() {
+        panic!("transpositions are not supported by the linear space method");
+    }
+
+    if options.linear_space && options.affine {
+        panic!("affine gaps are not supported by the linear space method");
+    }
+
+    if options.scope.is_some() && (options.linear_space || options.affine) {
+        panic!("local and semi-global alignment only support the full grid with linear gaps");
+    }
+
+    if options.all_optimal && (options.linear_space || options.affine || options.scope.is_some()) {
+        panic!("all optimal alignments are only found for the edit distance with the full grid");
     }
 
     for line in lines {
@@ -659,147 +868,150 @@ fn main() {
             panic!("invalid test string provided: \"{}\"", valid);
         };
 
-        if !from.is_ascii() {
-            panic!("string a contains non ascii characters");
-        }
+        let from_tokens = tokens::split(from, options.segmentation);
+        let to_tokens = tokens::split(to, options.segmentation);
 
-        if !to.is_ascii() {
-            panic!("string b contains non ascii characters");
+        if let Some(matrix) = &options.matrix {
+            if let Err(err) = matrix.check(&from_tokens).and_then(|_| matrix.check(&to_tokens)) {
+                panic!("invalid substitution matrix: {err}");
+            }
         }
 
-        let from_bytes = from.as_bytes();
-        let to_bytes = to.as_bytes();
-
-        let rtn = edit_distance(from_bytes, to_bytes, ins, del, sub, verbose);
-        let result = rtn.result;
-
-        if verbose {
-            let longest = rtn.longest;
-            let leading_width = (to_bytes.len().ilog10() + 1) as usize;
-
-            let dash_spacer = "-".repeat(longest);
-            let spacer = " ".repeat(longest);
-            let leading_dash_spacer = "-".repeat(leading_width);
-            let leading_spacer = " ".repeat(leading_width);
-
-            print!(" {leading_spacer}    |");
+        if options.all_optimal {
+            let subs = substitution(&options.matrix, &costs);
+            let rtn = edit_distance(&from_tokens, &to_tokens, &costs, subs, options.verbose);
 
-            for col in 0..=from_bytes.len() {
-                print!(" {col:longest$} ");
+            if options.verbose {
+                print_grid(&from_tokens, &to_tokens, &rtn.result, rtn.longest);
             }
 
-            println!("");
-            print!(" {leading_spacer}    |");
-
-            for index in 0..=from_bytes.len() {
-                if index == 0 {
-                    print!(" {spacer} ");
-                } else {
-                    print!(" {:>longest$} ", char::from(from_bytes[index - 1]));
-                }
+            // a transposition lines up the same as two substitutions so
+            // different edits can give the same alignment. one more than the
+            // limit is kept to know if it was reached
+            let mut seen = std::collections::HashSet::new();
+            let found: Vec<(String, String)> = optimal::Alignments::new(&rtn.result)
+                .map(|edits| build_alignment(&from_tokens, &to_tokens, &edits, &options.gap))
+                .filter(|pair| seen.insert(pair.clone()))
+                .take(options.limit.saturating_add(1))
+                .collect();
+
+            if found.len() > options.limit {
+                println!("alignments: {} (limit reached)", options.limit);
+            } else {
+                println!("alignments: {}", found.len());
             }
 
-            println!("");
-            print!("-{leading_dash_spacer}----+");
-
-            for _ in 0..=from_bytes.len() {
-                print!("-{dash_spacer}-");
+            for (from_output, to_output) in found.into_iter().take(options.limit) {
+                println!("{from_output}\n{to_output}");
             }
 
-            println!("");
+            println!("{}", rtn.result[to_tokens.len()][from_tokens.len()].value);
 
-            for (index, row) in result.iter().enumerate() {
-                if index == 0 {
-                    print!(" {index:leading_width$}    |");
-                } else {
-                    print!(" {index:leading_width$}  {} |", char::from(to_bytes[index - 1]));
-                }
+            continue;
+        }
 
-                for pair in row {
-                    print!(" {:longest$}{}", pair.value, pair.kind);
-                }
+        if let (Some(scope), Some(scores)) = (options.scope, &scores) {
+            let subs = substitution(&options.matrix, scores);
+            let found = alignment::align(&from_tokens, &to_tokens, scores, subs, scope, options.verbose);
+            let (from_output, to_output) = build_alignment(
+                &from_tokens[found.from.clone()],
+                &to_tokens[found.to.clone()],
+                &found.edits,
+                &options.gap
+            );
 
-                println!("");
-            }
+            println!("{from_output}\n{to_output}\n{}", found.score);
+            println!("from: {}..{}", found.from.start, found.from.end);
+            println!("to: {}..{}", found.to.start, found.to.end);
+
+            continue;
         }
 
-        let mut from_index = from_bytes.len();
-        let mut to_index = to_bytes.len();
-        let edit_value = result[to_bytes.len()][from_bytes.len()].value;
+        let (edit_value, edits) = if let Some(gap_costs) = &gap_costs {
+            let subs = substitution(&options.matrix, gap_costs);
 
-        let mut from_output = Vec::new();
-        let mut to_output = Vec::new();
+            affine::edit_distance(&from_tokens, &to_tokens, gap_costs, subs)
+        } else if options.linear_space {
+            // every pair of tokens is compared about twice so they are
+            // swapped for ids that are cheaper to compare than strings
+            let mut ids = std::collections::HashMap::new();
+            let from_ids = token_ids(&from_tokens, &mut ids);
+            let to_ids = token_ids(&to_tokens, &mut ids);
+            let subs = ById::new(&ids, substitution(&options.matrix, &costs));
 
-        while from_index != 0 && to_index != 0 {
-            if verbose {
-                print!("indexs: {from_index}:{to_index}");
-            }
+            hirschberg::edit_distance(&from_ids, &to_ids, &costs, &subs)
+        } else {
+            let subs = substitution(&options.matrix, &costs);
 
-            match result[to_index][from_index].kind {
-                EditKind::Mat => {
-                    if verbose {
-                        print!(" mat");
-                    }
+            full_edit_distance(&from_tokens, &to_tokens, &costs, subs, options.verbose)
+        };
 
-                    from_output.push(from_bytes[from_index - 1]);
-                    to_output.push(to_bytes[to_index - 1]);
-                    from_index -= 1;
-                    to_index -= 1;
-                }
-                EditKind::Sub => {
-                    if verbose {
-                        print!(" sub");
-                    }
+        let (from_output, to_output) = build_alignment(&from_tokens, &to_tokens, &edits, &options.gap);
 
-                    from_output.push(from_bytes[from_index - 1]);
-                    to_output.push(to_bytes[to_index - 1]);
-                    from_index -= 1;
-                    to_index -= 1;
-                }
-                EditKind::Ins => {
-                    if verbose {
-                        print!(" ins");
-                    }
+        println!("{from_output}\n{to_output}\n{edit_value}");
+    }
+}
 
-                    from_output.push(b'_');
-                    to_output.push(to_bytes[to_index - 1]);
-                    to_index -= 1;
-                }
-                EditKind::Del => {
-                    if verbose {
-                        print!(" del");
-                    }
+/// the most differences that the myers method is used for before falling
+/// back to the linear space method. the myers method keeps O(D^2) values so
+/// this caps it at a few million
+const MAX_FAST_DIFFERENCES: usize = 2048;
 
-                    from_output.push(from_bytes[from_index - 1]);
-                    to_output.push(b'_');
-                    from_index -= 1;
-                },
-            }
+fn read_file(path: &str) -> String {
+    match std::fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(err) => panic!("failed to read \"{path}\": {err}"),
+    }
+}
 
-            if verbose {
-                println!(" -> {from_index}:{to_index}");
-            }
-        }
+/// prints the changes between the two files as unified diff hunks
+///
+/// files with only a few differences use the myers method. anything else
+/// uses the linear space method with insertions and deletions costing 1 and
+/// a substitution costing the same as a deletion and insertion together.
+fn diff_main(from_path: &str, to_path: &str, context: usize) {
+    let from_text = read_file(from_path);
+    let to_text = read_file(to_path);
+
+    let from: Vec<&str> = from_text.lines().collect();
+    let to: Vec<&str> = to_text.lines().collect();
+
+    let mut ids = std::collections::HashMap::new();
+    let from_ids = token_ids(&from, &mut ids);
+    let to_ids = token_ids(&to, &mut ids);
+
+    let edits = match myers::diff(&from_ids, &to_ids, MAX_FAST_DIFFERENCES) {
+        Some(edits) => edits,
+        None => {
+            let costs = Costs {
+                ins: 1,
+                del: 1,
+                sub: 2,
+                tra: None,
+            };
 
-        while from_index != 0 {
-            from_output.push(from_bytes[from_index - 1]);
-            to_output.push(b'_');
-            from_index -= 1;
-        }
+            hirschberg::edit_distance(&from_ids, &to_ids, &costs, &costs).1
+        },
+    };
 
-        while to_index != 0 {
-            from_output.push(b'_');
-            to_output.push(to_bytes[to_index - 1]);
-            to_index -= 1;
-        }
+    let hunks = diff::hunks(&edits, context);
 
-        from_output.reverse();
-        to_output.reverse();
+    if hunks.is_empty() {
+        return;
+    }
+
+    println!("--- {from_path}");
+    println!("+++ {to_path}");
+
+    for hunk in hunks {
+        println!("{hunk}");
 
-        println!(
-            "{}\n{}\n{edit_value}",
-            std::str::from_utf8(&from_output).unwrap(),
-            std::str::from_utf8(&to_output).unwrap()
-        );
+        for line in hunk.lines {
+            match line {
+                diff::Line::Context(index) => println!(" {}", from[index]),
+                diff::Line::Removed(index) => println!("-{}", from[index]),
+                diff::Line::Added(index) => println!("+{}", to[index]),
+            }
+        }
     }
 }
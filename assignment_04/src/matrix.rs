@@ -0,0 +1,212 @@
+use std::collections::HashMap;
+
+use crate::Cost;
+
+/// the cost or score of aligning a token of one input with a token of the
+/// other
+///
+/// the alignment methods only need tokens that can be compared. anything
+/// that depends on what the tokens are is found through this instead
+pub trait Substitution<T> {
+    fn value(&self, a: &T, b: &T) -> Cost;
+}
+
+/// a problem found with a substitution matrix or the tokens aligned with it
+#[derive(Debug)]
+pub enum MatrixError {
+    /// the file has no header line of symbols
+    Empty,
+    /// a symbol is listed more than once in the header or as a row
+    DuplicateSymbol {
+        symbol: String,
+    },
+    /// a row starts with a symbol that is not in the header
+    UnknownRow {
+        symbol: String,
+    },
+    /// a row does not have a value for every symbol in the header
+    RowLength {
+        symbol: String,
+        expected: usize,
+        found: usize,
+    },
+    InvalidValue {
+        symbol: String,
+        value: String,
+    },
+    /// a symbol in the header has no row
+    MissingRow {
+        symbol: String,
+    },
+    /// substituting a for b has to cost the same as substituting b for a
+    Asymmetric {
+        a: String,
+        b: String,
+    },
+    /// a token that is being aligned is not in the matrix
+    MissingSymbol {
+        symbol: String,
+    },
+}
+
+impl std::fmt::Display for MatrixError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MatrixError::Empty => {
+                write!(f, "no symbols provided")
+            },
+            MatrixError::DuplicateSymbol { symbol } => {
+                write!(f, "symbol \"{symbol}\" is given more than once")
+            },
+            MatrixError::UnknownRow { symbol } => {
+                write!(f, "row \"{symbol}\" is not in the header")
+            },
+            MatrixError::RowLength { symbol, expected, found } => {
+                write!(f, "row \"{symbol}\" has {found} values. expected {expected}")
+            },
+            MatrixError::InvalidValue { symbol, value } => {
+                write!(f, "row \"{symbol}\" has an invalid value: \"{value}\"")
+            },
+            MatrixError::MissingRow { symbol } => {
+                write!(f, "symbol \"{symbol}\" has no row")
+            },
+            MatrixError::Asymmetric { a, b } => {
+                write!(f, "\"{a}\" to \"{b}\" does not match \"{b}\" to \"{a}\"")
+            },
+            MatrixError::MissingSymbol { symbol } => {
+                write!(f, "symbol \"{symbol}\" is not in the matrix")
+            },
+        }
+    }
+}
+
+/// the value of substituting every pair of symbols
+///
+/// the text format is the one used for blosum and pam matrices. the first
+/// line lists the symbols and every line after it starts with a symbol
+/// followed by the value for each symbol in the header. blank lines and
+/// lines starting with `#` are skipped. every symbol needs a row and the
+/// matrix has to be symmetric so the order that two tokens are compared in
+/// never matters.
+pub struct SubstitutionMatrix {
+    indices: HashMap<String, usize>,
+    /// `values[a * len + b]` is the value of substituting a with b
+    values: Vec<Cost>,
+}
+
+impl SubstitutionMatrix {
+    pub fn parse(text: &str) -> Result<Self, MatrixError> {
+        let mut lines = text.lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'));
+
+        let header: Vec<&str> = lines.next()
+            .ok_or(MatrixError::Empty)?
+            .split_whitespace()
+            .collect();
+
+        let mut indices = HashMap::with_capacity(header.len());
+
+        for (index, symbol) in header.iter().enumerate() {
+            if indices.insert(symbol.to_string(), index).is_some() {
+                return Err(MatrixError::DuplicateSymbol {
+                    symbol: symbol.to_string(),
+                });
+            }
+        }
+
+        let len = header.len();
+        let mut values = vec![0; len * len];
+        let mut found = vec![false; len];
+
+        for line in lines {
+            let mut split = line.split_whitespace();
+            let symbol = split.next().unwrap();
+
+            let Some(row) = indices.get(symbol).copied() else {
+                return Err(MatrixError::UnknownRow {
+                    symbol: symbol.to_owned(),
+                });
+            };
+
+            if found[row] {
+                return Err(MatrixError::DuplicateSymbol {
+                    symbol: symbol.to_owned(),
+                });
+            }
+
+            found[row] = true;
+
+            let list: Vec<&str> = split.collect();
+
+            if list.len() != len {
+                return Err(MatrixError::RowLength {
+                    symbol: symbol.to_owned(),
+                    expected: len,
+                    found: list.len(),
+                });
+            }
+
+            for (col, value) in list.into_iter().enumerate() {
+                let Ok(parsed) = value.parse() else {
+                    return Err(MatrixError::InvalidValue {
+                        symbol: symbol.to_owned(),
+                        value: value.to_owned(),
+                    });
+                };
+
+                values[row * len + col] = parsed;
+            }
+        }
+
+        if let Some(row) = found.iter().position(|check| !check) {
+            return Err(MatrixError::MissingRow {
+                symbol: header[row].to_owned(),
+            });
+        }
+
+        for a in 0..len {
+            for b in (a + 1)..len {
+                if values[a * len + b] != values[b * len + a] {
+                    return Err(MatrixError::Asymmetric {
+                        a: header[a].to_owned(),
+                        b: header[b].to_owned(),
+                    });
+                }
+            }
+        }
+
+        Ok(SubstitutionMatrix {
+            indices,
+            values,
+        })
+    }
+
+    /// checks that every token has an entry in the matrix
+    pub fn check<T: AsRef<str>>(&self, tokens: &[T]) -> Result<(), MatrixError> {
+        for token in tokens {
+            if !self.indices.contains_key(token.as_ref()) {
+                return Err(MatrixError::MissingSymbol {
+                    symbol: token.as_ref().to_owned(),
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// the value of substituting a with b. both are expected to have been
+    /// checked already
+    pub fn get(&self, a: &str, b: &str) -> Cost {
+        let a = self.indices[a];
+        let b = self.indices[b];
+
+        self.values[a * self.indices.len() + b]
+    }
+}
+
+impl<T: AsRef<str>> Substitution<T> for SubstitutionMatrix {
+    fn value(&self, a: &T, b: &T) -> Cost {
+        self.get(a.as_ref(), b.as_ref())
+    }
+}
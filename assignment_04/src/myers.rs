@@ -0,0 +1,98 @@
+use crate::EditKind;
+
+/// the furthest point reached along diagonal `k` by each round, stored for
+/// k from -d to d
+type Round = Vec<isize>;
+
+/// finds the fewest insertions and deletions that turn from in to to with
+/// myers' O(ND) method, None if more than `max_differences` are needed
+///
+/// every round allows one more difference and follows each diagonal of the
+/// grid as far as matching tokens allow, so the work done depends on the
+/// number of differences instead of the size of the grid. the furthest
+/// point of every round is kept to walk back from the end which takes
+/// O(D^2) memory, so the amount of differences is capped by the caller.
+/// a deletion is preferred over an insertion when both are optimal.
+pub fn diff<T: Eq>(from: &[T], to: &[T], max_differences: usize) -> Option<Vec<EditKind>> {
+    let from_len = from.len() as isize;
+    let to_len = to.len() as isize;
+    let limit = max_differences.min(from.len() + to.len()) as isize;
+
+    // diagonal k is stored at k + offset and k can reach one past the limit
+    let offset = limit + 1;
+    let mut furthest = vec![0; (2 * limit + 3) as usize];
+    let mut rounds: Vec<Round> = Vec::new();
+
+    for d in 0..=limit {
+        for k in (-d..=d).step_by(2) {
+            let down = k == -d ||
+                (k != d && furthest[(k - 1 + offset) as usize] < furthest[(k + 1 + offset) as usize]);
+
+            let mut x = if down {
+                furthest[(k + 1 + offset) as usize]
+            } else {
+                furthest[(k - 1 + offset) as usize] + 1
+            };
+            let mut y = x - k;
+
+            while x < from_len && y < to_len && from[x as usize] == to[y as usize] {
+                x += 1;
+                y += 1;
+            }
+
+            furthest[(k + offset) as usize] = x;
+
+            if x >= from_len && y >= to_len {
+                return Some(walk_back(&rounds, from_len, to_len, d));
+            }
+        }
+
+        rounds.push(furthest[(offset - d) as usize..=(offset + d) as usize].to_vec());
+    }
+
+    None
+}
+
+/// walks back from the end through the furthest points of each round,
+/// returning the edits in the order that they are applied
+fn walk_back(rounds: &[Round], from_len: isize, to_len: isize, last: isize) -> Vec<EditKind> {
+    let mut edits = Vec::new();
+    let mut x = from_len;
+    let mut y = to_len;
+
+    for d in (1..=last).rev() {
+        let prev = &rounds[(d - 1) as usize];
+        let get = |k: isize| prev[(k + d - 1) as usize];
+        let k = x - y;
+
+        let prev_k = if k == -d || (k != d && get(k - 1) < get(k + 1)) {
+            k + 1
+        } else {
+            k - 1
+        };
+
+        let prev_x = get(prev_k);
+        let prev_y = prev_x - prev_k;
+
+        while x > prev_x && y > prev_y {
+            edits.push(EditKind::Mat);
+            x -= 1;
+            y -= 1;
+        }
+
+        if prev_k == k + 1 {
+            edits.push(EditKind::Ins);
+        } else {
+            edits.push(EditKind::Del);
+        }
+
+        x = prev_x;
+        y = prev_y;
+    }
+
+    // everything before the first difference is a match
+    edits.extend(std::iter::repeat_n(EditKind::Mat, x as usize));
+    edits.reverse();
+
+    edits
+}
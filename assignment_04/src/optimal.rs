@@ -0,0 +1,92 @@
+use crate::{Edit, EditKind};
+
+/// a choice still to be tried while walking back through the grid
+struct Branch {
+    from_index: usize,
+    to_index: usize,
+    /// the amount of edits taken before reaching the cell
+    depth: usize,
+    kind: EditKind,
+}
+
+/// lazily walks every optimal path back through a filled edit distance grid,
+/// yielding the edits of each alignment in the order they are applied
+///
+/// each cell holds every edit that reaches it with the optimal value. the
+/// walk is a depth first search that tries the edit the normal traceback
+/// would follow first, so the first alignment is always the one that the
+/// normal traceback finds. only the current path and the choices not yet
+/// tried are kept so the amount of alignments can be far larger than what
+/// fits in memory.
+pub(crate) struct Alignments<'a> {
+    grid: &'a [Vec<Edit>],
+    /// the edits of the current path from the end back towards the start
+    path: Vec<EditKind>,
+    pending: Vec<Branch>,
+}
+
+impl<'a> Alignments<'a> {
+    pub(crate) fn new(grid: &'a [Vec<Edit>]) -> Self {
+        let to_index = grid.len() - 1;
+        let from_index = grid[0].len() - 1;
+
+        let mut rtn = Alignments {
+            grid,
+            path: Vec::with_capacity(from_index + to_index),
+            pending: Vec::new(),
+        };
+
+        rtn.push_choices(from_index, to_index, 0);
+        rtn
+    }
+
+    /// adds every optimal edit of the cell so that the one stored as the
+    /// kind of the cell is tried first and the rest follow in a fixed order
+    fn push_choices(&mut self, from_index: usize, to_index: usize, depth: usize) {
+        let edit = &self.grid[to_index][from_index];
+        let others = edit.optimal.iter()
+            .rev()
+            .filter(|kind| *kind != edit.kind);
+
+        // the last choice pushed is the first one tried
+        for kind in others.chain(std::iter::once(edit.kind)) {
+            self.pending.push(Branch {
+                from_index,
+                to_index,
+                depth,
+                kind,
+            });
+        }
+    }
+}
+
+impl Iterator for Alignments<'_> {
+    type Item = Vec<EditKind>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // the grid of two empty inputs only has the single empty alignment
+        if self.grid.len() == 1 && self.grid[0].len() == 1 {
+            return self.pending.pop().map(|_| Vec::new());
+        }
+
+        while let Some(branch) = self.pending.pop() {
+            self.path.truncate(branch.depth);
+            self.path.push(branch.kind);
+
+            let (from_index, to_index) = match branch.kind {
+                EditKind::Mat | EditKind::Sub => (branch.from_index - 1, branch.to_index - 1),
+                EditKind::Del => (branch.from_index - 1, branch.to_index),
+                EditKind::Ins => (branch.from_index, branch.to_index - 1),
+                EditKind::Tra => (branch.from_index - 2, branch.to_index - 2),
+            };
+
+            if from_index == 0 && to_index == 0 {
+                return Some(self.path.iter().rev().copied().collect());
+            }
+
+            self.push_choices(from_index, to_index, self.path.len());
+        }
+
+        None
+    }
+}
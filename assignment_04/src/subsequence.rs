@@ -0,0 +1,112 @@
+/// the direction that a monotonic subsequence moves in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Increasing,
+    Decreasing,
+}
+
+/// a subsequence of a list along with where each value was found
+#[derive(Debug, Clone)]
+pub struct Subsequence<T> {
+    pub indices: Vec<usize>,
+    pub values: Vec<T>,
+}
+
+impl<T> Subsequence<T> {
+    pub fn len(&self) -> usize {
+        self.indices.len()
+    }
+}
+
+/// the result of patience sorting a list
+pub struct Piles {
+    /// the length of the longest subsequence that ends at each index
+    pub lengths: Vec<usize>,
+    /// the index before each index in the longest subsequence ending there
+    prev: Vec<Option<usize>>,
+    /// the index that the longest subsequence overall ends at
+    last: Option<usize>,
+}
+
+impl Piles {
+    /// follows the previous indices back from the given index, returning the
+    /// indices of the longest subsequence ending there in ascending order
+    pub fn chain_to(&self, index: usize) -> Vec<usize> {
+        let mut rtn = Vec::new();
+        let mut current = Some(index);
+
+        while let Some(index) = current {
+            rtn.push(index);
+            current = self.prev[index];
+        }
+
+        rtn.reverse();
+        rtn
+    }
+}
+
+/// checks if a is allowed to come before b in the subsequence
+fn comes_before<T: Ord>(a: &T, b: &T, direction: Direction, strict: bool) -> bool {
+    match (direction, strict) {
+        (Direction::Increasing, true) => a < b,
+        (Direction::Increasing, false) => a <= b,
+        (Direction::Decreasing, true) => a > b,
+        (Direction::Decreasing, false) => a >= b,
+    }
+}
+
+/// finds the longest monotonic subsequence ending at every index with
+/// patience sorting
+///
+/// `tails[k]` holds the index of the best value to end a subsequence of
+/// length k + 1 with. the tails are always sorted in the direction of the
+/// subsequence so the pile that a value goes on is found with a binary
+/// search which makes the whole search O(n log n). the value on the pile
+/// before it is the previous value of the subsequence.
+pub fn patience_sort<T: Ord>(list: &[T], direction: Direction, strict: bool) -> Piles {
+    let mut tails: Vec<usize> = Vec::new();
+    let mut lengths = vec![0; list.len()];
+    let mut prev = vec![None; list.len()];
+
+    for (index, value) in list.iter().enumerate() {
+        let pile = tails.partition_point(|tail| comes_before(&list[*tail], value, direction, strict));
+
+        if pile > 0 {
+            prev[index] = Some(tails[pile - 1]);
+        }
+
+        if pile == tails.len() {
+            tails.push(index);
+        } else {
+            tails[pile] = index;
+        }
+
+        lengths[index] = pile + 1;
+    }
+
+    Piles {
+        lengths,
+        prev,
+        last: tails.last().copied(),
+    }
+}
+
+/// finds one of the longest monotonic subsequences of the list in
+/// O(n log n)
+pub fn longest<T: Ord + Clone>(list: &[T], direction: Direction, strict: bool) -> Subsequence<T> {
+    let piles = patience_sort(list, direction, strict);
+
+    let indices = match piles.last {
+        Some(last) => piles.chain_to(last),
+        None => Vec::new(),
+    };
+
+    let values = indices.iter()
+        .map(|index| list[*index].clone())
+        .collect();
+
+    Subsequence {
+        indices,
+        values,
+    }
+}
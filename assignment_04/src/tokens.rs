@@ -0,0 +1,22 @@
+use unicode_segmentation::UnicodeSegmentation;
+
+/// how a string is split in to the tokens that get aligned
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Segmentation {
+    /// every unicode scalar value is a token
+    Chars,
+    /// every extended grapheme cluster is a token so that a letter and the
+    /// combining marks on it are inserted or deleted together
+    Graphemes,
+}
+
+/// splits the string in to tokens, each one being a slice of the original
+/// string
+pub fn split(value: &str, segmentation: Segmentation) -> Vec<&str> {
+    match segmentation {
+        Segmentation::Chars => value.char_indices()
+            .map(|(index, ch)| &value[index..index + ch.len_utf8()])
+            .collect(),
+        Segmentation::Graphemes => value.graphemes(true).collect(),
+    }
+}